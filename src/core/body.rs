@@ -1,13 +1,16 @@
 use avian2d::prelude::*;
 use bevy::prelude::*;
 
+use super::status_effects::StatusEffects;
+
 pub struct BodyPlugin;
 
 impl Plugin for BodyPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, apply_max_speed)
+        app.add_systems(Update, (decay_knockback, apply_max_speed).chain())
             .add_observer(insert_max_linear_speed)
-            .register_type::<MovementConfig>();
+            .register_type::<MovementConfig>()
+            .register_type::<Knockback>();
     }
 }
 
@@ -38,12 +41,73 @@ pub struct MovementConfig {
     pub acceleration: f32,
 }
 
-fn apply_max_speed(
-    mut query: Query<(&mut MaxLinearSpeed, &MovementConfig), Changed<MovementConfig>>,
-) {
-    for (mut max_speed, config) in query.iter_mut() {
-        info!("Applying max speed: {}", config.max_speed);
-        max_speed.0 = config.max_speed;
+/// Velocity pushed onto a body by hits, on top of its own movement. It dies off quickly, and is
+/// allowed to take the body past its max speed while it lasts.
+#[derive(Component, Reflect, Default)]
+pub struct Knockback {
+    velocity: Vec2,
+    /// Fraction of every impulse that's ignored
+    pub resistance: f32,
+}
+
+/// Knockback velocity drops by this factor every second
+const KNOCKBACK_DECAY: f32 = 8.0;
+const KNOCKBACK_MIN_SPEED: f32 = 1.0;
+
+impl Knockback {
+    pub fn with_resistance(resistance: f32) -> Self {
+        Knockback {
+            velocity: Vec2::ZERO,
+            resistance,
+        }
+    }
+
+    pub fn apply(&mut self, impulse: Vec2, linear_velocity: &mut LinearVelocity) {
+        let impulse = impulse * (1.0 - self.resistance).clamp(0.0, 1.0);
+        self.velocity += impulse;
+        linear_velocity.0 += impulse;
+    }
+}
+
+fn decay_knockback(time: Res<Time>, mut query: Query<(&mut Knockback, &mut LinearVelocity)>) {
+    for (mut knockback, mut linear_velocity) in query.iter_mut() {
+        if knockback.velocity == Vec2::ZERO {
+            continue;
+        }
+        let previous = knockback.velocity;
+        let mut velocity = previous * (-KNOCKBACK_DECAY * time.delta_secs()).exp();
+        if velocity.length() < KNOCKBACK_MIN_SPEED {
+            velocity = Vec2::ZERO;
+        }
+        // Only take back what's left of the knockback, leaving the body's own movement alone
+        linear_velocity.0 -= previous - velocity;
+        knockback.velocity = velocity;
+    }
+}
+
+type SpeedChanged = Or<(
+    Changed<MovementConfig>,
+    Changed<StatusEffects>,
+    Changed<Knockback>,
+)>;
+
+type MaxSpeedData<'a> = (
+    &'a mut MaxLinearSpeed,
+    &'a MovementConfig,
+    Option<&'a StatusEffects>,
+    Option<&'a Knockback>,
+);
+
+fn apply_max_speed(mut query: Query<MaxSpeedData, SpeedChanged>) {
+    for (mut max_speed, config, status_effects, knockback) in query.iter_mut() {
+        let speed = config.max_speed
+            * status_effects.map_or(1.0, |status_effects| status_effects.speed_multiplier())
+            + knockback.map_or(0.0, |knockback| knockback.velocity.length());
+        if max_speed.0 != speed {
+            // Changes every frame while being knocked back, so keep it out of the normal logs
+            debug!("Applying max speed: {speed}");
+            max_speed.0 = speed;
+        }
     }
 }
 
@@ -15,6 +15,7 @@ impl Plugin for CoreComponentsPlugin {
 
 #[derive(Event, Reflect)]
 pub struct DeathEvent {
+    pub entity: Entity,
     pub position: Vec3,
 }
 
@@ -52,9 +53,10 @@ impl ItemStore {
         self.current = self.max;
     }
 
-    pub fn try_take(&mut self) -> bool {
-        if self.current > 0 {
-            self.current -= 1;
+    /// Takes `amount` items, or none at all if there aren't that many
+    pub fn try_take_n(&mut self, amount: usize) -> bool {
+        if self.current >= amount {
+            self.current -= amount;
             true
         } else {
             false
@@ -77,6 +79,7 @@ fn die_when_dead(
             Death::Dying => (),
             Death::Dead => {
                 commands.trigger(DeathEvent {
+                    entity,
                     position: transform.translation,
                 });
                 commands.entity(entity).despawn();
@@ -113,9 +116,14 @@ impl ExperienceLevel {
         self.current_xp += amount;
         while self.current_xp >= self.xp_to_next_level {
             self.current_xp -= self.xp_to_next_level;
-            self.level += 1;
-            self.xp_to_next_level *= 1.5;
+            self.level_up();
         }
         (self.level - initial_level) as usize
     }
+
+    /// Goes up a level straight away, keeping any progress towards the next one
+    pub fn level_up(&mut self) {
+        self.level += 1;
+        self.xp_to_next_level *= 1.5;
+    }
 }
@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+
+pub struct HitFlashPlugin;
+
+impl Plugin for HitFlashPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<HitFlash>()
+            .add_systems(Update, tick_hit_flashes);
+    }
+}
+
+/// Briefly tints a sprite white after taking a hit, then puts the original colour back
+#[derive(Component, Reflect)]
+pub struct HitFlash {
+    timer: Timer,
+    original_color: Color,
+}
+
+const FLASH_DURATION_S: f32 = 0.08;
+/// Sprite colour multiplies the texture, so going well past 1 washes it out to white
+const FLASH_COLOR: Color = Color::linear_rgb(6.0, 6.0, 6.0);
+
+impl HitFlash {
+    pub fn new(original_color: Color) -> Self {
+        HitFlash {
+            timer: Timer::from_seconds(FLASH_DURATION_S, TimerMode::Once),
+            original_color,
+        }
+    }
+
+    pub fn restart(&mut self) {
+        self.timer.reset();
+    }
+}
+
+fn tick_hit_flashes(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut flashes: Query<(Entity, &mut HitFlash, &mut Sprite)>,
+) {
+    for (entity, mut flash, mut sprite) in flashes.iter_mut() {
+        flash.timer.tick(time.delta());
+        if flash.timer.is_finished() {
+            sprite.color = flash.original_color;
+            commands.entity(entity).remove::<HitFlash>();
+        } else {
+            sprite.color = FLASH_COLOR;
+        }
+    }
+}
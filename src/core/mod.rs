@@ -1,6 +1,8 @@
 pub mod body;
 pub mod components;
 pub mod directional_animation;
+pub mod hit_flash;
+pub mod status_effects;
 pub mod ui_components;
 
 pub fn build(app: &mut bevy::app::App) {
@@ -8,4 +10,6 @@ pub fn build(app: &mut bevy::app::App) {
     app.add_plugins(body::BodyPlugin);
     app.add_plugins(directional_animation::DirectionalAnimationPlugin);
     app.add_plugins(ui_components::CoreUiComponentsPlugin);
+    app.add_plugins(status_effects::StatusEffectsPlugin);
+    app.add_plugins(hit_flash::HitFlashPlugin);
 }
@@ -0,0 +1,292 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{camera::GAME_RENDER_LAYER, player_levelup::not_leveling_up};
+
+pub struct StatusEffectsPlugin;
+
+impl Plugin for StatusEffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<StatusEffects>()
+            .register_type::<StatusEffectIndicator>()
+            .add_systems(
+                Update,
+                (
+                    // Damage over time is only built up here, so this pauses it too
+                    tick_status_effects.run_if(not_leveling_up),
+                    update_status_effect_indicators,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Damage over time effects deal their damage in chunks this often, rather than every frame
+const DAMAGE_TICK_S: f32 = 0.5;
+const MAX_POISON_STACKS: usize = 5;
+
+#[derive(Deserialize, Serialize, Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatusEffectKind {
+    /// Reduces max speed by `magnitude` (0.0 - 1.0)
+    Slow,
+    /// Deals `magnitude` damage per second
+    Burn,
+    /// Deals `magnitude` damage per second, stacking with other poisons
+    Poison,
+    /// Prevents all movement and attacks
+    Stun,
+}
+
+impl StatusEffectKind {
+    pub const ALL: [StatusEffectKind; 4] = [
+        StatusEffectKind::Slow,
+        StatusEffectKind::Burn,
+        StatusEffectKind::Poison,
+        StatusEffectKind::Stun,
+    ];
+
+    fn stacks(&self) -> bool {
+        matches!(self, StatusEffectKind::Poison)
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            StatusEffectKind::Slow => Color::srgb(0.4, 0.7, 1.0),
+            StatusEffectKind::Burn => Color::srgb(1.0, 0.5, 0.1),
+            StatusEffectKind::Poison => Color::srgb(0.5, 0.9, 0.2),
+            StatusEffectKind::Stun => Color::srgb(1.0, 1.0, 0.4),
+        }
+    }
+}
+
+/// A timed effect, as carried by hits and configured in assets
+#[derive(Deserialize, Serialize, Reflect, Clone, Copy, Debug, PartialEq)]
+pub struct StatusEffect {
+    pub kind: StatusEffectKind,
+    pub duration_s: f32,
+    /// Meaning depends on the kind, see `StatusEffectKind`
+    #[serde(default)]
+    pub magnitude: f32,
+}
+
+#[derive(Reflect, Debug)]
+struct ActiveStatusEffect {
+    effect: StatusEffect,
+    remaining: Timer,
+}
+
+/// Timed effects currently applied to an entity.
+///
+/// Poison stacks up to a limit with each stack keeping its own duration, every other kind
+/// refreshes: the strongest magnitude and longest remaining duration are kept.
+#[derive(Component, Reflect, Debug)]
+pub struct StatusEffects {
+    active: Vec<ActiveStatusEffect>,
+    damage_timer: Timer,
+    pending_damage: f32,
+}
+
+impl Default for StatusEffects {
+    fn default() -> Self {
+        StatusEffects {
+            active: Vec::new(),
+            damage_timer: Timer::from_seconds(DAMAGE_TICK_S, TimerMode::Repeating),
+            pending_damage: 0.0,
+        }
+    }
+}
+
+impl StatusEffects {
+    pub fn apply(&mut self, effect: StatusEffect) {
+        let mut existing = self
+            .active
+            .iter_mut()
+            .filter(|active| active.effect.kind == effect.kind);
+        if effect.kind.stacks() {
+            let stacks: Vec<_> = existing.collect();
+            if stacks.len() >= MAX_POISON_STACKS {
+                // Replace the stack closest to running out
+                if let Some(oldest) = stacks
+                    .into_iter()
+                    .min_by_key(|active| active.remaining.remaining())
+                {
+                    *oldest = ActiveStatusEffect::new(effect);
+                }
+                return;
+            }
+        } else if let Some(active) = existing.next() {
+            active.effect.magnitude = active.effect.magnitude.max(effect.magnitude);
+            if active.remaining.remaining_secs() < effect.duration_s {
+                active.remaining = Timer::from_seconds(effect.duration_s, TimerMode::Once);
+            }
+            return;
+        }
+        self.active.push(ActiveStatusEffect::new(effect));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.active.is_empty()
+    }
+
+    pub fn has(&self, kind: StatusEffectKind) -> bool {
+        self.active.iter().any(|active| active.effect.kind == kind)
+    }
+
+    pub fn is_stunned(&self) -> bool {
+        self.has(StatusEffectKind::Stun)
+    }
+
+    /// Multiplier to apply to movement speed
+    pub fn speed_multiplier(&self) -> f32 {
+        if self.is_stunned() {
+            return 0.0;
+        }
+        let slow = self
+            .active
+            .iter()
+            .filter(|active| active.effect.kind == StatusEffectKind::Slow)
+            .map(|active| active.effect.magnitude)
+            .fold(0.0, f32::max);
+        1.0 - slow.clamp(0.0, 1.0)
+    }
+
+    /// Takes the damage over time dealt since the last call, for the owner to apply as a hit
+    pub fn take_pending_damage(&mut self) -> Option<f32> {
+        if self.pending_damage > 0.0 {
+            Some(std::mem::take(&mut self.pending_damage))
+        } else {
+            None
+        }
+    }
+
+    fn tick(&mut self, delta: std::time::Duration) {
+        if self.damage_timer.tick(delta).just_finished() {
+            let damage_per_second: f32 = self
+                .active
+                .iter()
+                .filter(|active| {
+                    matches!(
+                        active.effect.kind,
+                        StatusEffectKind::Burn | StatusEffectKind::Poison
+                    )
+                })
+                .map(|active| active.effect.magnitude)
+                .sum();
+            self.pending_damage += damage_per_second * DAMAGE_TICK_S;
+        }
+        for active in self.active.iter_mut() {
+            active.remaining.tick(delta);
+        }
+        self.active.retain(|active| !active.remaining.is_finished());
+    }
+}
+
+impl ActiveStatusEffect {
+    fn new(effect: StatusEffect) -> Self {
+        ActiveStatusEffect {
+            effect,
+            remaining: Timer::from_seconds(effect.duration_s, TimerMode::Once),
+        }
+    }
+}
+
+fn tick_status_effects(time: Res<Time>, mut query: Query<&mut StatusEffects>) {
+    for mut status_effects in query.iter_mut() {
+        if !status_effects.is_empty() {
+            status_effects.tick(time.delta());
+        }
+    }
+}
+
+/// Small coloured marker shown above an entity for each kind of effect on it
+#[derive(Component, Reflect)]
+pub struct StatusEffectIndicator(StatusEffectKind);
+
+fn update_status_effect_indicators(
+    mut commands: Commands,
+    query: Query<(Entity, &StatusEffects, Option<&Children>), Changed<StatusEffects>>,
+    indicators: Query<&StatusEffectIndicator>,
+) {
+    for (entity, status_effects, children) in query.iter() {
+        let shown: Vec<(Entity, StatusEffectKind)> = children
+            .into_iter()
+            .flatten()
+            .filter_map(|child| indicators.get(*child).ok().map(|ind| (*child, ind.0)))
+            .collect();
+        let active: Vec<StatusEffectKind> = StatusEffectKind::ALL
+            .into_iter()
+            .filter(|kind| status_effects.has(*kind))
+            .collect();
+        if shown
+            .iter()
+            .map(|(_, kind)| *kind)
+            .eq(active.iter().copied())
+        {
+            continue;
+        }
+        for (indicator, _) in shown {
+            commands.entity(indicator).despawn();
+        }
+        let width = active.len() as f32 * 3.0;
+        for (i, kind) in active.into_iter().enumerate() {
+            let x = i as f32 * 3.0 - width / 2.0 + 1.5;
+            commands.entity(entity).with_child((
+                Name::new("Status Effect Indicator"),
+                StatusEffectIndicator(kind),
+                Sprite::from_color(kind.color(), Vec2::splat(2.0)),
+                Transform::from_xyz(x, 10.0, 1.0),
+                GAME_RENDER_LAYER,
+            ));
+        }
+    }
+}
+
+#[test]
+fn poison_stacks_to_a_cap_and_slow_refreshes() {
+    use std::time::Duration;
+
+    let poison = |duration_s| StatusEffect {
+        kind: StatusEffectKind::Poison,
+        duration_s,
+        magnitude: 2.0,
+    };
+    let mut poisoned = StatusEffects::default();
+    for i in 0..MAX_POISON_STACKS {
+        poisoned.apply(poison(1.0 + i as f32));
+    }
+    // Past the cap, the stack closest to running out is replaced
+    poisoned.apply(poison(10.0));
+    assert_eq!(poisoned.active.len(), MAX_POISON_STACKS);
+    assert!(
+        poisoned
+            .active
+            .iter()
+            .all(|active| active.remaining.remaining_secs() >= 2.0)
+    );
+    poisoned.tick(Duration::from_secs_f32(DAMAGE_TICK_S));
+    let stack_damage = 2.0 * DAMAGE_TICK_S;
+    assert_eq!(
+        poisoned.take_pending_damage(),
+        Some(stack_damage * MAX_POISON_STACKS as f32)
+    );
+    assert_eq!(poisoned.take_pending_damage(), None);
+
+    let mut slowed = StatusEffects::default();
+    slowed.apply(StatusEffect {
+        kind: StatusEffectKind::Slow,
+        duration_s: 3.0,
+        magnitude: 0.5,
+    });
+    slowed.apply(StatusEffect {
+        kind: StatusEffectKind::Slow,
+        duration_s: 1.0,
+        magnitude: 0.2,
+    });
+    // Keeps the strongest magnitude and the longest duration
+    assert_eq!(slowed.active.len(), 1);
+    assert_eq!(slowed.speed_multiplier(), 0.5);
+    slowed.tick(Duration::from_secs(2));
+    assert!(slowed.has(StatusEffectKind::Slow));
+    slowed.tick(Duration::from_secs(2));
+    assert!(slowed.is_empty());
+}
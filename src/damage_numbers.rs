@@ -0,0 +1,177 @@
+use bevy::prelude::*;
+
+use crate::{GameState, camera::GAME_RENDER_LAYER};
+
+pub struct DamageNumbersPlugin;
+
+impl Plugin for DamageNumbersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DamageNumberSettings>()
+            .init_resource::<DamageNumberPool>()
+            .add_message::<ShowDamageNumber>()
+            .register_type::<DamageNumberSettings>()
+            .register_type::<DamageNumber>()
+            .add_systems(
+                Update,
+                (show_damage_numbers, animate_damage_numbers)
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub struct DamageNumberSettings {
+    pub enabled: bool,
+}
+
+impl Default for DamageNumberSettings {
+    fn default() -> Self {
+        DamageNumberSettings { enabled: true }
+    }
+}
+
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DamageNumberStyle {
+    Normal,
+    Crit,
+    /// Damage taken by the player
+    Player,
+}
+
+impl DamageNumberStyle {
+    fn color(&self) -> Color {
+        match self {
+            DamageNumberStyle::Normal => Color::WHITE,
+            DamageNumberStyle::Crit => Color::srgb(1.0, 0.85, 0.2),
+            DamageNumberStyle::Player => Color::srgb(1.0, 0.25, 0.25),
+        }
+    }
+
+    fn scale(&self) -> f32 {
+        match self {
+            DamageNumberStyle::Normal => 1.0,
+            DamageNumberStyle::Crit | DamageNumberStyle::Player => 1.5,
+        }
+    }
+}
+
+#[derive(Message, Debug)]
+pub struct ShowDamageNumber {
+    pub position: Vec3,
+    pub damage: f32,
+    pub style: DamageNumberStyle,
+}
+
+#[derive(Component, Reflect)]
+struct DamageNumber {
+    timer: Timer,
+    scale: f32,
+}
+
+/// Number entities are recycled rather than spawned per hit, so there are never more than this
+const MAX_DAMAGE_NUMBERS: usize = 128;
+const LIFETIME_S: f32 = 0.8;
+const RISE_SPEED: f32 = 20.0;
+/// Text is rendered at a large font size then scaled down, so it stays sharp under the zoomed in
+/// camera
+const FONT_SIZE: f32 = 32.0;
+const TEXT_SCALE: f32 = 0.15;
+/// Above everything else in the world
+const DAMAGE_NUMBER_Z: f32 = 200.0;
+
+/// Ring buffer of damage number entities, the oldest is reused once it's full
+#[derive(Resource, Default)]
+struct DamageNumberPool {
+    entities: Vec<Entity>,
+    next: usize,
+}
+
+type DamageNumberData<'a> = (
+    &'a mut DamageNumber,
+    &'a mut Text2d,
+    &'a mut TextColor,
+    &'a mut Transform,
+    &'a mut Visibility,
+);
+
+fn show_damage_numbers(
+    mut commands: Commands,
+    settings: Res<DamageNumberSettings>,
+    mut events: MessageReader<ShowDamageNumber>,
+    mut pool: ResMut<DamageNumberPool>,
+    mut numbers: Query<DamageNumberData>,
+) {
+    if !settings.enabled {
+        events.clear();
+        return;
+    }
+    for event in events.read() {
+        let text = format!("{:.0}", event.damage.max(1.0));
+        let color = event.style.color();
+        let scale = event.style.scale() * TEXT_SCALE;
+        let mut transform = Transform::from_translation(event.position.with_z(DAMAGE_NUMBER_Z))
+            .with_scale(Vec3::splat(scale));
+        // Spread out numbers from hits landing together
+        let slot = pool.entities.len().min(MAX_DAMAGE_NUMBERS) + pool.next;
+        transform.translation.x += (slot % 5) as f32 * 2.0 - 4.0;
+
+        if pool.entities.len() < MAX_DAMAGE_NUMBERS {
+            let entity = commands
+                .spawn((
+                    Name::new("Damage Number"),
+                    DamageNumber {
+                        timer: Timer::from_seconds(LIFETIME_S, TimerMode::Once),
+                        scale,
+                    },
+                    Text2d::new(text),
+                    TextFont {
+                        font_size: FONT_SIZE,
+                        ..default()
+                    },
+                    TextColor(color),
+                    transform,
+                    GAME_RENDER_LAYER,
+                ))
+                .id();
+            pool.entities.push(entity);
+            continue;
+        }
+        let entity = pool.entities[pool.next];
+        pool.next = (pool.next + 1) % MAX_DAMAGE_NUMBERS;
+        if let Ok((
+            mut number,
+            mut number_text,
+            mut number_color,
+            mut number_transform,
+            mut visibility,
+        )) = numbers.get_mut(entity)
+        {
+            number.timer.reset();
+            number.scale = scale;
+            number_text.0 = text;
+            number_color.0 = color;
+            *number_transform = transform;
+            *visibility = Visibility::Inherited;
+        }
+    }
+}
+
+fn animate_damage_numbers(time: Res<Time>, mut numbers: Query<DamageNumberData>) {
+    for (mut number, _, mut color, mut transform, mut visibility) in numbers.iter_mut() {
+        if number.timer.is_finished() {
+            continue;
+        }
+        number.timer.tick(time.delta());
+        if number.timer.is_finished() {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+        let progress = number.timer.fraction();
+        transform.translation.y += RISE_SPEED * (1.0 - progress) * time.delta_secs();
+        // Pop in slightly larger, then settle
+        transform.scale = Vec3::splat(number.scale * (1.0 + 0.5 * (1.0 - progress).powi(4)));
+        color.0.set_alpha(1.0 - progress.powi(2));
+    }
+}
@@ -14,6 +14,8 @@ pub struct DropsPlugin;
 impl Plugin for DropsPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Drop>()
+            .register_type::<ExperienceDrop>()
+            .register_type::<LevelUpDrop>()
             .add_systems(Update, move_pickup)
             .add_observer(spawn_drop_on_death);
     }
@@ -24,9 +26,18 @@ pub struct Drop {
     picked_up_by: Option<Entity>,
 }
 
+/// Experience dropped by an entity when it dies
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+pub struct ExperienceDrop(pub f32);
+
+/// Treasure dropped by an entity when it dies, granting a number of level ups
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+pub struct LevelUpDrop(pub u32);
+
 #[derive(Component, Reflect, Clone, Debug)]
 pub enum DropKind {
     Experience { value: f32 },
+    LevelUp { count: u32 },
 }
 
 #[derive(EntityEvent, Reflect)]
@@ -36,26 +47,54 @@ pub struct PickupEvent {
     pub picked_up_by: Entity,
 }
 
+fn drop_bundle(position: Vec3, scale: f32, sprite: Sprite, drop_kind: DropKind) -> impl Bundle {
+    (
+        Transform::from_translation(position).with_scale(Vec3::splat(scale)),
+        sprite,
+        Drop::default(),
+        drop_kind,
+        crate::collisions::game_drop_layer(),
+        Collider::circle(3.0),
+        body::body(body::BodyKind::Dynamic),
+        LinearVelocity::default(),
+        GAME_RENDER_LAYER,
+    )
+}
+
 fn spawn_drop_on_death(
     death_event: On<DeathEvent>,
     mut commands: Commands,
+    drops: Query<(Option<&ExperienceDrop>, Option<&LevelUpDrop>)>,
     textures: Res<crate::loading::TextureAssets>,
 ) {
-    let transform =
-        Transform::from_translation(death_event.event().position).with_scale(Vec3::splat(0.3));
-    commands
-        .spawn((
-            transform,
-            Sprite::from_image(textures.xp_gem.clone()),
-            Drop::default(),
-            DropKind::Experience { value: 10. },
-            crate::collisions::game_drop_layer(),
-            Collider::circle(3.0),
-            body::body(body::BodyKind::Dynamic),
-            LinearVelocity::default(),
-            GAME_RENDER_LAYER,
-        ))
-        .observe(on_in_pickup_range);
+    let Ok((experience_drop, level_up_drop)) = drops.get(death_event.entity) else {
+        return;
+    };
+    let position = death_event.event().position;
+    if let Some(ExperienceDrop(value)) = experience_drop {
+        commands
+            .spawn(drop_bundle(
+                position,
+                // Bigger gems for more valuable drops, e.g. from elites
+                0.3 * (value / 10.0).sqrt().clamp(1.0, 2.0),
+                Sprite::from_image(textures.xp_gem.clone()),
+                DropKind::Experience { value: *value },
+            ))
+            .observe(on_in_pickup_range);
+    }
+    if let Some(LevelUpDrop(count)) = level_up_drop {
+        commands
+            .spawn(drop_bundle(
+                position + Vec3::new(8.0, 0.0, 0.0),
+                0.6,
+                Sprite {
+                    color: Color::srgb(1.0, 0.85, 0.2),
+                    ..Sprite::from_image(textures.xp_gem.clone())
+                },
+                DropKind::LevelUp { count: *count },
+            ))
+            .observe(on_in_pickup_range);
+    }
 }
 
 fn on_in_pickup_range(
@@ -90,6 +129,12 @@ pub fn on_pickup(
                     level_up_messages.write(crate::player_levelup::LeveledUp {});
                 }
             }
+            DropKind::LevelUp { count } => {
+                for _ in 0..*count {
+                    exp_level.level_up();
+                    level_up_messages.write(crate::player_levelup::LeveledUp {});
+                }
+            }
         }
     }
 }
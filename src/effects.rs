@@ -0,0 +1,329 @@
+use std::collections::HashMap;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_hanabi::prelude::*;
+
+use crate::{
+    GameState,
+    camera::GAME_RENDER_LAYER,
+    core::components::DeathEvent,
+    drops::{DropKind, PickupEvent},
+    enemy::Enemy,
+    player::Player,
+    player_levelup::LeveledUp,
+    projectile::{EnemyHit, Reloaded, explosion::Explosion},
+};
+
+pub struct EffectsPlugin;
+
+impl Plugin for EffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(HanabiPlugin)
+            .init_resource::<EffectPools>()
+            .register_type::<EffectEmitter>()
+            .add_systems(Startup, setup_effects)
+            .add_systems(
+                Update,
+                (
+                    impact_sparks,
+                    explosion_burst,
+                    level_up_burst,
+                    reload_complete,
+                )
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_observer(death_burst)
+            .add_observer(xp_sparkle);
+    }
+}
+
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EffectKind {
+    DeathBurst,
+    ImpactSparks,
+    /// Bigger, brighter sparks for a critical hit
+    CritBurst,
+    Explosion,
+    XpSparkle,
+    LevelUpBurst,
+    ReloadComplete,
+}
+
+impl EffectKind {
+    const ALL: [EffectKind; 7] = [
+        EffectKind::DeathBurst,
+        EffectKind::ImpactSparks,
+        EffectKind::CritBurst,
+        EffectKind::Explosion,
+        EffectKind::XpSparkle,
+        EffectKind::LevelUpBurst,
+        EffectKind::ReloadComplete,
+    ];
+
+    /// Emitters kept around for this effect, so that many can play at once
+    fn pool_size(&self) -> usize {
+        match self {
+            EffectKind::DeathBurst => 16,
+            EffectKind::ImpactSparks => 32,
+            EffectKind::CritBurst => 16,
+            EffectKind::Explosion => 8,
+            EffectKind::XpSparkle => 8,
+            EffectKind::LevelUpBurst | EffectKind::ReloadComplete => 2,
+        }
+    }
+
+    fn burst(&self) -> Burst {
+        match self {
+            EffectKind::DeathBurst => Burst {
+                count: 40.0,
+                speed: (20.0, 50.0),
+                lifetime: 0.6,
+                drag: 4.0,
+                rise: 0.0,
+                size: 1.5,
+                color: Vec4::new(1.0, 0.4, 0.3, 1.0),
+            },
+            EffectKind::ImpactSparks => Burst {
+                count: 8.0,
+                speed: (30.0, 60.0),
+                lifetime: 0.25,
+                drag: 6.0,
+                rise: 0.0,
+                size: 0.6,
+                color: Vec4::new(2.0, 1.8, 1.0, 1.0),
+            },
+            EffectKind::CritBurst => Burst {
+                count: 20.0,
+                speed: (40.0, 90.0),
+                lifetime: 0.35,
+                drag: 5.0,
+                rise: 0.0,
+                size: 1.0,
+                color: Vec4::new(2.5, 2.0, 0.4, 1.0),
+            },
+            EffectKind::Explosion => Burst {
+                count: 60.0,
+                speed: (40.0, 120.0),
+                lifetime: 0.4,
+                drag: 6.0,
+                rise: 0.0,
+                size: 2.0,
+                color: Vec4::new(2.5, 1.2, 0.3, 1.0),
+            },
+            EffectKind::XpSparkle => Burst {
+                count: 16.0,
+                speed: (5.0, 15.0),
+                lifetime: 0.7,
+                drag: 2.0,
+                rise: 20.0,
+                size: 0.8,
+                color: Vec4::new(0.4, 1.5, 1.2, 1.0),
+            },
+            EffectKind::LevelUpBurst => Burst {
+                count: 80.0,
+                speed: (40.0, 80.0),
+                lifetime: 1.0,
+                drag: 3.0,
+                rise: 10.0,
+                size: 1.2,
+                color: Vec4::new(2.0, 1.6, 0.4, 1.0),
+            },
+            EffectKind::ReloadComplete => Burst {
+                count: 12.0,
+                speed: (15.0, 15.0),
+                lifetime: 0.3,
+                drag: 0.0,
+                rise: 0.0,
+                size: 0.6,
+                color: Vec4::new(0.8, 0.9, 1.5, 1.0),
+            },
+        }
+    }
+}
+
+/// A one-off spray of particles out from a point, which shrink and fade over their lifetime
+struct Burst {
+    count: f32,
+    /// Min and max starting speed
+    speed: (f32, f32),
+    lifetime: f32,
+    drag: f32,
+    /// Upwards acceleration
+    rise: f32,
+    size: f32,
+    color: Vec4,
+}
+
+impl Burst {
+    fn effect_asset(&self, name: &str) -> EffectAsset {
+        let writer = ExprWriter::new();
+        let init_age = SetAttributeModifier::new(Attribute::AGE, writer.lit(0.0).expr());
+        let init_lifetime =
+            SetAttributeModifier::new(Attribute::LIFETIME, writer.lit(self.lifetime).expr());
+        let init_position = SetPositionCircleModifier {
+            center: writer.lit(Vec3::ZERO).expr(),
+            axis: writer.lit(Vec3::Z).expr(),
+            radius: writer.lit(1.0).expr(),
+            dimension: ShapeDimension::Volume,
+        };
+        let init_velocity = SetVelocityCircleModifier {
+            center: writer.lit(Vec3::ZERO).expr(),
+            axis: writer.lit(Vec3::Z).expr(),
+            speed: writer
+                .lit(self.speed.0)
+                .uniform(writer.lit(self.speed.1))
+                .expr(),
+        };
+        let drag = LinearDragModifier::new(writer.lit(self.drag).expr());
+        let rise = AccelModifier::new(writer.lit(Vec3::Y * self.rise).expr());
+
+        let mut color = bevy_hanabi::Gradient::new();
+        color.add_key(0.0, self.color);
+        color.add_key(1.0, self.color.with_w(0.0));
+
+        // Spawn only when told to, rather than as soon as the emitter exists
+        let spawner = SpawnerSettings::once(self.count.into()).with_emit_on_start(false);
+        EffectAsset::new(self.count as u32 * 4, spawner, writer.finish())
+            .with_name(name)
+            .init(init_position)
+            .init(init_velocity)
+            .init(init_age)
+            .init(init_lifetime)
+            .update(drag)
+            .update(rise)
+            .render(ColorOverLifetimeModifier::new(color))
+            .render(SizeOverLifetimeModifier {
+                gradient: bevy_hanabi::Gradient::linear(Vec3::splat(self.size), Vec3::ZERO),
+                screen_space_size: false,
+            })
+    }
+}
+
+#[derive(Component, Reflect)]
+struct EffectEmitter(EffectKind);
+
+/// Above everything else in the world
+const EFFECT_Z: f32 = 150.0;
+
+/// Ring buffer of emitters for each effect, the oldest is reused when a new one is played
+#[derive(Resource, Default)]
+struct EffectPools {
+    pools: HashMap<EffectKind, (Vec<Entity>, usize)>,
+}
+
+fn setup_effects(
+    mut commands: Commands,
+    mut effects: ResMut<Assets<EffectAsset>>,
+    mut pools: ResMut<EffectPools>,
+) {
+    for kind in EffectKind::ALL {
+        let effect = effects.add(kind.burst().effect_asset(&format!("{kind:?}")));
+        let emitters = (0..kind.pool_size())
+            .map(|_| {
+                commands
+                    .spawn((
+                        Name::new(format!("Effect Emitter {kind:?}")),
+                        EffectEmitter(kind),
+                        ParticleEffect::new(effect.clone()),
+                        Transform::from_translation(Vec3::Z * EFFECT_Z),
+                        GAME_RENDER_LAYER,
+                    ))
+                    .id()
+            })
+            .collect();
+        pools.pools.insert(kind, (emitters, 0));
+    }
+}
+
+#[derive(SystemParam)]
+pub struct Effects<'w, 's> {
+    pools: ResMut<'w, EffectPools>,
+    emitters: Query<
+        'w,
+        's,
+        (&'static mut Transform, Option<&'static mut EffectSpawner>),
+        With<EffectEmitter>,
+    >,
+}
+
+impl Effects<'_, '_> {
+    pub fn play(&mut self, kind: EffectKind, position: Vec3) {
+        let Some((emitters, next)) = self.pools.pools.get_mut(&kind) else {
+            return;
+        };
+        let Some(&emitter) = emitters.get(*next) else {
+            return;
+        };
+        *next = (*next + 1) % emitters.len();
+        // The spawner is added by hanabi a frame after the emitter, so effects in the very first
+        // frame are dropped
+        if let Ok((mut transform, Some(mut spawner))) = self.emitters.get_mut(emitter) {
+            transform.translation = position.with_z(EFFECT_Z);
+            spawner.reset();
+        }
+    }
+}
+
+fn death_burst(death_event: On<DeathEvent>, enemies: Query<(), With<Enemy>>, mut effects: Effects) {
+    if enemies.contains(death_event.entity) {
+        effects.play(EffectKind::DeathBurst, death_event.position);
+    }
+}
+
+fn impact_sparks(
+    mut hit_events: MessageReader<EnemyHit>,
+    enemies: Query<&Transform, (With<Enemy>, Without<EffectEmitter>)>,
+    mut effects: Effects,
+) {
+    for event in hit_events.read() {
+        if let Ok(transform) = enemies.get(event.enemy_entity) {
+            let kind = if event.is_crit {
+                EffectKind::CritBurst
+            } else {
+                EffectKind::ImpactSparks
+            };
+            effects.play(kind, transform.translation);
+        }
+    }
+}
+
+fn explosion_burst(mut explosions: MessageReader<Explosion>, mut effects: Effects) {
+    for explosion in explosions.read() {
+        effects.play(EffectKind::Explosion, explosion.position.extend(0.0));
+    }
+}
+
+fn xp_sparkle(
+    pickup: On<PickupEvent>,
+    transforms: Query<&Transform, Without<EffectEmitter>>,
+    mut effects: Effects,
+) {
+    if let DropKind::Experience { .. } = pickup.drop_kind
+        && let Ok(transform) = transforms.get(pickup.picked_up_by)
+    {
+        effects.play(EffectKind::XpSparkle, transform.translation);
+    }
+}
+
+fn level_up_burst(
+    mut level_ups: MessageReader<LeveledUp>,
+    player: Single<&Transform, (With<Player>, Without<EffectEmitter>)>,
+    mut effects: Effects,
+) {
+    // A pile of level ups at once is still just one burst
+    if level_ups.read().count() > 0 {
+        effects.play(EffectKind::LevelUpBurst, player.translation);
+    }
+}
+
+fn reload_complete(
+    mut reloads: MessageReader<Reloaded>,
+    transforms: Query<&Transform, Without<EffectEmitter>>,
+    mut effects: Effects,
+) {
+    for reloaded in reloads.read() {
+        if let Ok(transform) = transforms.get(reloaded.entity) {
+            effects.play(EffectKind::ReloadComplete, transform.translation);
+        }
+    }
+}
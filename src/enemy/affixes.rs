@@ -0,0 +1,189 @@
+use bevy::prelude::*;
+use rand::{RngExt, seq::IndexedRandom};
+use serde::{Deserialize, Serialize};
+
+use super::{Enemy, SpawnEnemy, split::SplitOnDeath};
+use crate::{
+    core::{
+        body::MovementConfig,
+        components::{CollidesWithPlayer, DeathEvent, Health},
+    },
+    player::Player,
+    projectile::PlayerHit,
+};
+
+pub struct AffixesPlugin;
+
+impl Plugin for AffixesPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Elite>()
+            .register_type::<Armored>()
+            .register_type::<Vampiric>()
+            .register_type::<ExplodeOnDeath>()
+            .add_systems(Update, vampiric_heal)
+            .add_observer(explode_on_death);
+    }
+}
+
+/// Modifiers that can be rolled onto a spawned enemy to make it an elite
+#[derive(Deserialize, Serialize, Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Affix {
+    Fast,
+    Armored,
+    Splitting,
+    Vampiric,
+    Explosive,
+}
+
+impl Affix {
+    pub const ALL: [Affix; 5] = [
+        Affix::Fast,
+        Affix::Armored,
+        Affix::Splitting,
+        Affix::Vampiric,
+        Affix::Explosive,
+    ];
+
+    pub fn tint(&self) -> Color {
+        match self {
+            Affix::Fast => Color::srgb(0.5, 0.8, 1.0),
+            Affix::Armored => Color::srgb(0.6, 0.6, 0.7),
+            Affix::Splitting => Color::srgb(0.7, 1.0, 0.5),
+            Affix::Vampiric => Color::srgb(0.9, 0.3, 0.4),
+            Affix::Explosive => Color::srgb(1.0, 0.6, 0.2),
+        }
+    }
+
+    /// Adjusts the stats an enemy is spawned with
+    pub fn modify_stats(
+        &self,
+        health: &mut Health,
+        movement: &mut MovementConfig,
+        contact: &mut CollidesWithPlayer,
+    ) {
+        match self {
+            Affix::Fast => {
+                movement.max_speed *= 1.6;
+                movement.acceleration *= 1.6;
+            }
+            Affix::Armored => {
+                *health = Health::new(health.max * 2.0);
+                movement.max_speed *= 0.8;
+            }
+            Affix::Splitting => {
+                *health = Health::new(health.max * 1.5);
+            }
+            Affix::Vampiric => {
+                contact.damage *= 1.5;
+            }
+            Affix::Explosive => {
+                movement.max_speed *= 1.2;
+            }
+        }
+    }
+
+    /// Inserts the component that implements the affix's behaviour
+    pub(super) fn insert_behaviour(&self, enemy: &mut EntityCommands, spawn: &SpawnEnemy) {
+        match self {
+            Affix::Fast => {}
+            Affix::Armored => {
+                enemy.insert(Armored {
+                    damage_reduction: 0.4,
+                });
+            }
+            Affix::Splitting => {
+                // Stacks with any splitting the archetype already does
+                enemy
+                    .entry::<SplitOnDeath>()
+                    .and_modify(|mut split| split.count += 2)
+                    .or_insert(SplitOnDeath {
+                        archetype: spawn.archetype.clone(),
+                        count: 2,
+                        size: spawn.size * 0.7,
+                        generation: spawn.generation + 1,
+                    });
+            }
+            Affix::Vampiric => {
+                enemy.insert(Vampiric {
+                    heal_per_damage: 5.0,
+                });
+            }
+            Affix::Explosive => {
+                enemy.insert(ExplodeOnDeath {
+                    radius: 30.0,
+                    damage: 2.0,
+                });
+            }
+        }
+    }
+
+    /// Rolls up to `max_affixes` distinct affixes with the given chance of rolling any at all
+    pub fn roll(rng: &mut impl rand::Rng, elite_chance: f32, max_affixes: usize) -> Vec<Affix> {
+        if max_affixes == 0 || rng.random::<f32>() >= elite_chance {
+            return Vec::new();
+        }
+        let count = rng.random_range(1..=max_affixes.min(Affix::ALL.len()));
+        Affix::ALL.sample(rng, count).copied().collect()
+    }
+}
+
+/// Marks an enemy that rolled affixes
+#[derive(Component, Reflect)]
+pub struct Elite {
+    pub affixes: Vec<Affix>,
+}
+
+/// Reduces all incoming damage by a fraction
+#[derive(Component, Reflect)]
+pub struct Armored {
+    pub damage_reduction: f32,
+}
+
+/// Heals when dealing damage to the player
+#[derive(Component, Reflect)]
+pub struct Vampiric {
+    pub heal_per_damage: f32,
+}
+
+/// Damages the player if they're nearby when this dies
+#[derive(Component, Reflect)]
+pub struct ExplodeOnDeath {
+    pub radius: f32,
+    pub damage: f32,
+}
+
+fn vampiric_heal(
+    mut hit_events: MessageReader<PlayerHit>,
+    mut vampires: Query<(&Vampiric, &mut Health), With<Enemy>>,
+) {
+    for event in hit_events.read() {
+        if let Some(source) = event.source
+            && let Ok((vampiric, mut health)) = vampires.get_mut(source)
+        {
+            health.current =
+                (health.current + event.damage * vampiric.heal_per_damage).min(health.max);
+        }
+    }
+}
+
+fn explode_on_death(
+    death_event: On<DeathEvent>,
+    explosives: Query<&ExplodeOnDeath>,
+    player_transform: Single<&Transform, With<Player>>,
+    mut player_hits: MessageWriter<PlayerHit>,
+) {
+    let Ok(explosive) = explosives.get(death_event.entity) else {
+        return;
+    };
+    let distance = player_transform
+        .translation
+        .truncate()
+        .distance(death_event.position.truncate());
+    if distance <= explosive.radius {
+        player_hits.write(PlayerHit {
+            damage: explosive.damage,
+            source: None,
+            status_effect: None,
+        });
+    }
+}
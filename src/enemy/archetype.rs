@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{boss::BossDefinition, split::SplitDefinition};
+use crate::core::{
+    body::MovementConfig,
+    directional_animation::{CharacterState, DirectionalAnimationAsset},
+    status_effects::StatusEffect,
+};
+
+/// Collider shapes that can be described in asset files
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Reflect)]
+pub enum ColliderShape {
+    Circle { radius: f32 },
+    Capsule { radius: f32, length: f32 },
+    Rectangle { width: f32, height: f32 },
+}
+
+impl ColliderShape {
+    pub fn to_collider(self) -> Collider {
+        match self {
+            ColliderShape::Circle { radius } => Collider::circle(radius),
+            ColliderShape::Capsule { radius, length } => Collider::capsule(radius, length),
+            ColliderShape::Rectangle { width, height } => Collider::rectangle(width, height),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Reflect)]
+pub struct MovementDefinition {
+    pub max_speed: f32,
+    pub acceleration: f32,
+}
+
+impl MovementDefinition {
+    pub fn to_config(self) -> MovementConfig {
+        MovementConfig {
+            max_speed: self.max_speed,
+            acceleration: self.acceleration,
+        }
+    }
+}
+
+/// How an enemy decides to move and attack
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, Reflect)]
+pub enum EnemyBehaviour {
+    /// Walk straight at the player and deal contact damage
+    #[default]
+    Melee,
+    /// Hold at a distance from the player and fire projectiles
+    Ranged(RangedConfig),
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Reflect)]
+pub struct RangedConfig {
+    /// Back away from the player when closer than this
+    pub keep_distance: f32,
+    /// Start aiming when the player is within this range
+    pub fire_range: f32,
+    pub aim_time_s: f32,
+    pub cooldown_s: f32,
+    pub projectile_speed: f32,
+    pub projectile_damage: f32,
+    #[serde(default)]
+    pub projectile_status_effect: Option<StatusEffect>,
+}
+
+/// An animation is either referenced by its key in the custom assets file, or defined inline.
+/// References are resolved when the custom assets file is registered.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum AnimationRef {
+    Key(String),
+    Inline(DirectionalAnimationAsset),
+}
+
+/// Serialised form of an enemy archetype, as written in the custom assets file
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct EnemyArchetypeDefinition {
+    pub health: f32,
+    pub collider: ColliderShape,
+    pub movement: MovementDefinition,
+    pub contact_damage: f32,
+    pub xp_value: f32,
+    #[serde(default)]
+    pub behaviour: EnemyBehaviour,
+    /// Uniform scale of the sprite and collider
+    #[serde(default = "default_scale")]
+    pub scale: f32,
+    /// Present if this archetype is a boss
+    #[serde(default)]
+    pub boss: Option<BossDefinition>,
+    /// Present if this archetype splits into smaller copies when it dies
+    #[serde(default)]
+    pub split: Option<SplitDefinition>,
+    pub animation: AnimationRef,
+    /// Texture paths per animation state
+    pub textures: HashMap<CharacterState, String>,
+}
+
+fn default_scale() -> f32 {
+    1.0
+}
+
+impl EnemyArchetypeDefinition {
+    pub fn texture_paths(&self) -> impl Iterator<Item = &String> {
+        self.textures.values()
+    }
+
+    pub fn build(
+        &self,
+        asset_server: &AssetServer,
+        animations: &mut Assets<DirectionalAnimationAsset>,
+    ) -> anyhow::Result<EnemyArchetype> {
+        let animation = match &self.animation {
+            AnimationRef::Inline(asset) => animations.add(asset.clone()),
+            AnimationRef::Key(key) => {
+                anyhow::bail!("Animation reference '{key}' was not resolved")
+            }
+        };
+        let textures = self
+            .textures
+            .iter()
+            .map(|(state, path)| (*state, asset_server.load(path)))
+            .collect();
+        Ok(EnemyArchetype {
+            health: self.health,
+            collider: self.collider,
+            movement: self.movement,
+            contact_damage: self.contact_damage,
+            xp_value: self.xp_value,
+            behaviour: self.behaviour,
+            scale: self.scale,
+            boss: self.boss.clone(),
+            split: self.split,
+            animation,
+            textures,
+        })
+    }
+}
+
+/// Runtime form of an enemy archetype, with all handles resolved
+#[derive(Debug, Clone)]
+pub struct EnemyArchetype {
+    pub health: f32,
+    pub collider: ColliderShape,
+    pub movement: MovementDefinition,
+    pub contact_damage: f32,
+    pub xp_value: f32,
+    pub behaviour: EnemyBehaviour,
+    pub scale: f32,
+    pub boss: Option<BossDefinition>,
+    pub split: Option<SplitDefinition>,
+    pub animation: Handle<DirectionalAnimationAsset>,
+    pub textures: HashMap<CharacterState, Handle<Image>>,
+}
+
+/// All enemy archetypes, keyed by their id in the custom assets file
+#[derive(Asset, TypePath, Debug, Default)]
+pub struct EnemyArchetypeLibrary(pub HashMap<String, EnemyArchetype>);
+
+impl EnemyArchetypeLibrary {
+    pub fn get(&self, id: &str) -> Option<&EnemyArchetype> {
+        self.0.get(id)
+    }
+}
@@ -0,0 +1,189 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{Enemy, SpawnEnemy, archetype::MovementDefinition};
+use crate::{
+    GameState,
+    core::{
+        body::MovementConfig,
+        components::{Death, Health},
+        status_effects::StatusEffects,
+    },
+    loading::TextureAssets,
+    player_levelup::not_leveling_up,
+    projectile::{self, ProjectileBehaviour, ProjectileStats, Team},
+};
+
+pub struct BossPlugin;
+
+impl Plugin for BossPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Boss>().add_systems(
+            Update,
+            (boss_phase_transitions, boss_phase_actions)
+                .chain()
+                .run_if(in_state(GameState::Playing).and(not_leveling_up)),
+        );
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Reflect)]
+pub struct SummonDefinition {
+    pub archetype: String,
+    pub count: usize,
+    pub interval_s: f32,
+}
+
+/// Fires `count` projectiles evenly around the boss, turning by `rotation_step` each volley
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Reflect)]
+pub struct ProjectilePatternDefinition {
+    pub count: usize,
+    pub interval_s: f32,
+    pub rotation_step: f32,
+    pub speed: f32,
+    pub damage: f32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Reflect)]
+pub struct BossPhaseDefinition {
+    /// The phase starts once health drops to this fraction of max health
+    pub health_threshold: f32,
+    #[serde(default)]
+    pub movement: Option<MovementDefinition>,
+    #[serde(default)]
+    pub summon: Option<SummonDefinition>,
+    #[serde(default)]
+    pub projectile_pattern: Option<ProjectilePatternDefinition>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Reflect)]
+pub struct BossDefinition {
+    pub name: String,
+    /// Phases in order, from the highest health threshold to the lowest
+    pub phases: Vec<BossPhaseDefinition>,
+    /// Number of level ups granted by the drop when the boss is killed
+    pub level_up_reward: u32,
+}
+
+#[derive(Component, Reflect)]
+pub struct Boss {
+    pub definition: BossDefinition,
+    phase: Option<usize>,
+    summon_timer: Timer,
+    pattern_timer: Timer,
+    pattern_angle: f32,
+}
+
+impl Boss {
+    pub fn new(definition: BossDefinition) -> Self {
+        Boss {
+            definition,
+            phase: None,
+            summon_timer: Timer::default(),
+            pattern_timer: Timer::default(),
+            pattern_angle: 0.0,
+        }
+    }
+
+    fn current_phase(&self) -> Option<&BossPhaseDefinition> {
+        self.phase
+            .and_then(|phase| self.definition.phases.get(phase))
+    }
+}
+
+type LivingBoss = (With<Enemy>, Without<Death>);
+
+fn boss_phase_transitions(
+    mut bosses: Query<(&mut Boss, &Health, &mut MovementConfig), LivingBoss>,
+) {
+    for (mut boss, health, mut movement_config) in bosses.iter_mut() {
+        let next_phase = boss.phase.map_or(0, |phase| phase + 1);
+        let Some(phase) = boss.definition.phases.get(next_phase) else {
+            continue;
+        };
+        if health.pct() > phase.health_threshold {
+            continue;
+        }
+        info!(
+            "Boss {} entering phase {}",
+            boss.definition.name, next_phase
+        );
+        if let Some(movement) = phase.movement {
+            *movement_config = movement.to_config();
+        }
+        let summon_interval = phase.summon.as_ref().map(|summon| summon.interval_s);
+        let pattern_interval = phase.projectile_pattern.map(|pattern| pattern.interval_s);
+        boss.phase = Some(next_phase);
+        if let Some(interval) = summon_interval {
+            boss.summon_timer = Timer::from_seconds(interval, TimerMode::Repeating);
+        }
+        if let Some(interval) = pattern_interval {
+            boss.pattern_timer = Timer::from_seconds(interval, TimerMode::Repeating);
+        }
+    }
+}
+
+fn boss_phase_actions(
+    mut commands: Commands,
+    time: Res<Time>,
+    textures: Res<TextureAssets>,
+    mut bosses: Query<(&mut Boss, &Transform, &StatusEffects), LivingBoss>,
+    mut spawn_enemies: MessageWriter<SpawnEnemy>,
+) {
+    for (mut boss, transform, status_effects) in bosses.iter_mut() {
+        // Summons and patterns wait out a stun, rather than firing the moment it ends
+        if status_effects.is_stunned() {
+            continue;
+        }
+        let Some(phase) = boss.current_phase().cloned() else {
+            continue;
+        };
+        let position = transform.translation.truncate();
+        if let Some(summon) = phase.summon
+            && boss.summon_timer.tick(time.delta()).just_finished()
+        {
+            for i in 0..summon.count {
+                let angle = TAU * i as f32 / summon.count as f32;
+                spawn_enemies.write(SpawnEnemy {
+                    global_position: position + Vec2::from_angle(angle) * 30.0,
+                    archetype: summon.archetype.clone(),
+                    affixes: Vec::new(),
+                    size: 1.0,
+                    generation: 0,
+                });
+            }
+        }
+        if let Some(pattern) = phase.projectile_pattern
+            && boss.pattern_timer.tick(time.delta()).just_finished()
+        {
+            for i in 0..pattern.count {
+                let angle = boss.pattern_angle + TAU * i as f32 / pattern.count as f32;
+                projectile::spawn_projectile(
+                    &mut commands,
+                    transform.translation,
+                    Vec2::from_angle(angle),
+                    ProjectileStats {
+                        speed: pattern.speed,
+                        damage: pattern.damage,
+                        crit_chance: 0.0,
+                        crit_multiplier: 1.0,
+                        pierce: 1,
+                        bounces: 0,
+                        team: Team::Enemy,
+                        status_effect: None,
+                        behaviour: ProjectileBehaviour::Straight,
+                        explosive: None,
+                        chain: None,
+                        range: projectile::ENEMY_PROJECTILE_RANGE,
+                        size: 1.0,
+                        knockback: 0.0,
+                    },
+                    &textures,
+                );
+            }
+            boss.pattern_angle += pattern.rotation_step;
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+};
+
+use bevy::prelude::*;
+use bevy_ecs_ldtk::GridCoords;
+
+use crate::{GameState, level_loader::Floor, player::Player, walls::Wall};
+
+pub struct FlowFieldPlugin;
+
+impl Plugin for FlowFieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FlowField>().add_systems(
+            Update,
+            update_flow_field.run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+const GRID_SIZE: IVec2 = IVec2::splat(8);
+const STRAIGHT_COST: u32 = 10;
+const DIAGONAL_COST: u32 = 14;
+
+/// A shared field of directions leading every walkable tile towards the player's tile.
+/// Rebuilt only when the player moves to a different tile, so following it is a lookup per enemy.
+#[derive(Resource, Default)]
+pub struct FlowField {
+    target: Option<GridCoords>,
+    directions: HashMap<GridCoords, Vec2>,
+}
+
+impl FlowField {
+    pub fn build(walkable: &HashSet<GridCoords>, target: GridCoords) -> Self {
+        let costs = Self::integrate(walkable, target);
+        let directions = costs
+            .keys()
+            .filter(|coords| **coords != target)
+            .filter_map(|coords| {
+                neighbours(walkable, *coords)
+                    .filter_map(|(neighbour, _)| costs.get(&neighbour).map(|c| (neighbour, *c)))
+                    .min_by_key(|(_, cost)| *cost)
+                    .map(|(neighbour, _)| {
+                        let offset = IVec2::from(neighbour) - IVec2::from(*coords);
+                        (*coords, offset.as_vec2().normalize())
+                    })
+            })
+            .collect();
+        FlowField {
+            target: Some(target),
+            directions,
+        }
+    }
+
+    /// Dijkstra outwards from the target, giving the cost to reach it from every walkable tile
+    fn integrate(walkable: &HashSet<GridCoords>, target: GridCoords) -> HashMap<GridCoords, u32> {
+        let mut costs = HashMap::new();
+        if !walkable.contains(&target) {
+            return costs;
+        }
+        let mut queue = BinaryHeap::new();
+        costs.insert(target, 0);
+        queue.push(Reverse((0, target.x, target.y)));
+        while let Some(Reverse((cost, x, y))) = queue.pop() {
+            let coords = GridCoords { x, y };
+            if costs.get(&coords).is_some_and(|best| cost > *best) {
+                continue;
+            }
+            for (neighbour, step) in neighbours(walkable, coords) {
+                let next = cost + step;
+                if costs.get(&neighbour).is_none_or(|best| next < *best) {
+                    costs.insert(neighbour, next);
+                    queue.push(Reverse((next, neighbour.x, neighbour.y)));
+                }
+            }
+        }
+        costs
+    }
+
+    /// World space direction to move in from the given position, if it is on the field
+    pub fn direction_at(&self, position: Vec2) -> Option<Vec2> {
+        let coords = bevy_ecs_ldtk::utils::translation_to_grid_coords(position, GRID_SIZE);
+        self.directions.get(&coords).copied()
+    }
+}
+
+/// Walkable neighbours of a tile with the cost of stepping to them.
+/// Diagonals are only allowed when both adjacent tiles are walkable, so paths don't cut wall corners.
+fn neighbours(
+    walkable: &HashSet<GridCoords>,
+    coords: GridCoords,
+) -> impl Iterator<Item = (GridCoords, u32)> + '_ {
+    let open = move |dx: i32, dy: i32| {
+        walkable.contains(&GridCoords {
+            x: coords.x + dx,
+            y: coords.y + dy,
+        })
+    };
+    [
+        (1, 0),
+        (-1, 0),
+        (0, 1),
+        (0, -1),
+        (1, 1),
+        (1, -1),
+        (-1, 1),
+        (-1, -1),
+    ]
+    .into_iter()
+    .filter(move |(dx, dy)| {
+        open(*dx, *dy) && (*dx == 0 || *dy == 0 || (open(*dx, 0) && open(0, *dy)))
+    })
+    .map(move |(dx, dy)| {
+        let cost = if dx == 0 || dy == 0 {
+            STRAIGHT_COST
+        } else {
+            DIAGONAL_COST
+        };
+        (
+            GridCoords {
+                x: coords.x + dx,
+                y: coords.y + dy,
+            },
+            cost,
+        )
+    })
+}
+
+type AddedTiles = Or<(Added<Floor>, Added<Wall>)>;
+
+fn update_flow_field(
+    mut flow_field: ResMut<FlowField>,
+    player_transform: Single<&Transform, With<Player>>,
+    floors: Query<&GridCoords, With<Floor>>,
+    walls: Query<&GridCoords, With<Wall>>,
+    added_tiles: Query<(), AddedTiles>,
+) {
+    let player_coords = bevy_ecs_ldtk::utils::translation_to_grid_coords(
+        player_transform.translation.truncate(),
+        GRID_SIZE,
+    );
+    if flow_field.target == Some(player_coords) && added_tiles.is_empty() {
+        return;
+    }
+    let wall_coords: HashSet<GridCoords> = walls.iter().copied().collect();
+    let walkable: HashSet<GridCoords> = floors
+        .iter()
+        .filter(|coords| !wall_coords.contains(coords))
+        .copied()
+        .collect();
+    *flow_field = FlowField::build(&walkable, player_coords);
+}
+
+#[test]
+fn flow_field_routes_around_walls() {
+    // A 3x3 room with a wall in the middle column, open only along the bottom row:
+    // . # T
+    // . # .
+    // . . .
+    let walkable: HashSet<GridCoords> = [(0, 0), (1, 0), (2, 0), (0, 1), (2, 1), (0, 2), (2, 2)]
+        .into_iter()
+        .map(|(x, y)| GridCoords { x, y })
+        .collect();
+    let field = FlowField::build(&walkable, GridCoords { x: 2, y: 2 });
+    let direction = |x, y| field.directions[&GridCoords { x, y }];
+    // From the top left, the only way is down around the wall
+    assert_eq!(direction(0, 2), Vec2::NEG_Y);
+    assert_eq!(direction(1, 0), Vec2::X);
+    assert_eq!(direction(2, 1), Vec2::Y);
+    assert!(!field.directions.contains_key(&GridCoords { x: 2, y: 2 }));
+}
@@ -0,0 +1,315 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    GameState,
+    camera::GAME_RENDER_LAYER,
+    core::{
+        body::{self, MovementConfig},
+        components::{CollidesWithPlayer, Death, Health},
+        directional_animation::{
+            CharacterState, CharacterStateMode, DirectionalAnimationAsset, OnOneShotEnd,
+            directional_animation_bundle,
+        },
+        hit_flash::HitFlash,
+        status_effects::StatusEffects,
+    },
+    damage_numbers::{DamageNumberStyle, ShowDamageNumber},
+    drops::{ExperienceDrop, LevelUpDrop},
+    player::Player,
+    projectile::EnemyHit,
+};
+
+pub mod affixes;
+pub mod archetype;
+pub mod boss;
+pub mod flow_field;
+pub mod ranged;
+pub mod split;
+pub mod steering;
+pub mod telegraph;
+pub mod wave_director;
+
+pub struct EnemyPlugin;
+
+#[derive(Component, Reflect)]
+pub struct Enemy;
+
+pub const ENEMY_Z: f32 = 99.0;
+/// Fraction of knockback ignored by bosses and elites
+const BOSS_KNOCKBACK_RESISTANCE: f32 = 0.9;
+const ELITE_KNOCKBACK_RESISTANCE: f32 = 0.5;
+
+impl Plugin for EnemyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (move_enemy, get_hit, status_effect_damage, spawn_enemies)
+                .run_if(in_state(GameState::Playing)),
+        )
+        .add_plugins((
+            wave_director::WaveDirectorPlugin,
+            flow_field::FlowFieldPlugin,
+            ranged::RangedEnemyPlugin,
+            boss::BossPlugin,
+            affixes::AffixesPlugin,
+            split::SplitPlugin,
+            steering::SteeringPlugin,
+            telegraph::TelegraphPlugin,
+        ))
+        .add_message::<SpawnEnemy>()
+        .register_type::<Enemy>();
+    }
+}
+
+#[derive(Message, Debug, Clone, Reflect)]
+struct SpawnEnemy {
+    global_position: Vec2,
+    /// Id of the archetype in the enemy archetype library
+    archetype: String,
+    affixes: Vec<affixes::Affix>,
+    /// Scales health, collider and sprite relative to the archetype
+    size: f32,
+    /// How many times this enemy's line has already split
+    generation: u32,
+}
+
+fn spawn_enemies(
+    mut events: MessageReader<SpawnEnemy>,
+    mut commands: Commands,
+    custom_assets: Res<crate::loading::CustomAssets>,
+    archetype_libraries: Res<Assets<archetype::EnemyArchetypeLibrary>>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    directional_animations: Res<Assets<DirectionalAnimationAsset>>,
+) -> Result {
+    let archetypes = archetype_libraries
+        .get(&custom_assets.enemy_archetypes)
+        .ok_or_else(|| anyhow::anyhow!("Enemy archetype library not loaded"))?;
+    for spawn in events.read() {
+        let SpawnEnemy {
+            global_position,
+            archetype: archetype_id,
+            affixes,
+            size,
+            generation,
+        } = spawn;
+        let translation = global_position.extend(ENEMY_Z);
+        println!("Spawning enemy {archetype_id} {affixes:?} at {translation:?}");
+        let archetype = archetypes
+            .get(archetype_id)
+            .ok_or_else(|| anyhow::anyhow!("Unknown enemy archetype {archetype_id}"))?;
+        let animation_bundle = {
+            let directional_animation_asset = directional_animations
+                .get(&archetype.animation)
+                .ok_or_else(|| anyhow::anyhow!("Enemy animation not loaded"))?;
+            directional_animation_bundle(
+                archetype.textures.clone(),
+                &mut texture_atlas_layouts,
+                directional_animation_asset,
+            )?
+        };
+        let mut health = Health::new(archetype.health * size);
+        let mut movement_config = archetype.movement.to_config();
+        let mut collides_with_player = CollidesWithPlayer {
+            damage: archetype.contact_damage,
+            last_collided: None,
+            damage_cooldown: std::time::Duration::from_secs(1),
+        };
+        for affix in affixes {
+            affix.modify_stats(&mut health, &mut movement_config, &mut collides_with_player);
+        }
+        // Elites are worth more experience for each affix they carry
+        let xp_value = archetype.xp_value * size * (1 + affixes.len()) as f32;
+        let knockback_resistance = if archetype.boss.is_some() {
+            BOSS_KNOCKBACK_RESISTANCE
+        } else if !affixes.is_empty() {
+            ELITE_KNOCKBACK_RESISTANCE
+        } else {
+            0.0
+        };
+        let mut enemy = commands.spawn((
+            Name::new("Enemy"),
+            health,
+            StatusEffects::default(),
+            Enemy,
+            Transform::from_translation(translation)
+                .with_scale(Vec3::splat(archetype.scale * size)),
+            archetype.collider.to_collider(),
+            body::body(body::BodyKind::Dynamic),
+            animation_bundle,
+            movement_config,
+            MaxLinearSpeed::default(),
+            body::Knockback::with_resistance(knockback_resistance),
+            collides_with_player,
+            ExperienceDrop(xp_value),
+            GAME_RENDER_LAYER,
+        ));
+        if let archetype::EnemyBehaviour::Ranged(config) = archetype.behaviour {
+            enemy.insert(ranged::RangedAttacker::new(config));
+        }
+        if let Some(boss) = &archetype.boss {
+            enemy.insert((
+                Name::new(format!("Boss: {}", boss.name)),
+                LevelUpDrop(boss.level_up_reward),
+                boss::Boss::new(boss.clone()),
+            ));
+        }
+        if let Some(split) = archetype.split
+            && *generation < split.generations
+        {
+            enemy.insert(split::SplitOnDeath {
+                archetype: archetype_id.clone(),
+                count: split.count,
+                size: size * split.size,
+                generation: generation + 1,
+            });
+        }
+        if let Some(first_affix) = affixes.first() {
+            for affix in affixes {
+                affix.insert_behaviour(&mut enemy, spawn);
+            }
+            let tint = first_affix.tint();
+            enemy
+                .insert((
+                    Name::new(format!("Elite Enemy {affixes:?}")),
+                    affixes::Elite {
+                        affixes: affixes.clone(),
+                    },
+                ))
+                .entry::<Sprite>()
+                .and_modify(move |mut sprite| sprite.color = tint);
+        }
+    }
+    Ok(())
+}
+
+type MeleeEnemy = (With<Enemy>, Without<ranged::RangedAttacker>);
+
+fn move_enemy(
+    time: Res<Time>,
+    flow_field: Res<flow_field::FlowField>,
+    spatial_hash: Res<steering::EnemySpatialHash>,
+    steering_config: Res<steering::SteeringConfig>,
+    player_transform: Single<&Transform, (With<Player>, Without<Enemy>)>,
+    mut enemy_query: Query<
+        (
+            Entity,
+            &mut LinearVelocity,
+            &Transform,
+            &MovementConfig,
+            Has<Death>,
+        ),
+        MeleeEnemy,
+    >,
+) -> Result {
+    for (entity, mut linear_velocity, transform, movement_config, is_dying) in
+        enemy_query.iter_mut()
+    {
+        match is_dying {
+            true => linear_velocity.0 = Vec2::ZERO,
+            false => {
+                let position = transform.translation.truncate();
+                // Off the field (or on the player's tile) head straight for the player
+                let direction_vec = flow_field.direction_at(position).unwrap_or_else(|| {
+                    (player_transform.translation.truncate() - position).normalize_or_zero()
+                });
+                // Steer around nearby enemies rather than relying on collisions to shove them apart
+                let steering = spatial_hash.steering(entity, position, &steering_config);
+                linear_velocity.0 +=
+                    (direction_vec + steering) * time.delta_secs() * movement_config.acceleration;
+            }
+        }
+    }
+    Ok(())
+}
+
+type HitEnemyData<'a> = (
+    &'a mut Health,
+    &'a mut CharacterStateMode,
+    &'a mut StatusEffects,
+    &'a Transform,
+    &'a Sprite,
+    &'a mut LinearVelocity,
+    &'a mut body::Knockback,
+    Option<&'a affixes::Armored>,
+);
+
+fn get_hit(
+    mut commands: Commands,
+    mut hit_events: MessageReader<EnemyHit>,
+    mut enemy_query: Query<HitEnemyData, With<Enemy>>,
+    mut damage_numbers: MessageWriter<ShowDamageNumber>,
+) {
+    for event in hit_events.read() {
+        if let Ok((
+            mut health,
+            mut state,
+            mut status_effects,
+            transform,
+            sprite,
+            mut linear_velocity,
+            mut knockback,
+            armored,
+        )) = enemy_query.get_mut(event.enemy_entity)
+        {
+            let reduction = armored.map_or(0.0, |armored| armored.damage_reduction);
+            let damage = event.damage * (1.0 - reduction);
+            health.current -= damage;
+            // Hits that only carry a status effect don't get a number
+            if damage > 0.0 {
+                damage_numbers.write(ShowDamageNumber {
+                    position: transform.translation,
+                    damage,
+                    style: if event.is_crit {
+                        DamageNumberStyle::Crit
+                    } else {
+                        DamageNumberStyle::Normal
+                    },
+                });
+            }
+            if let Some(status_effect) = event.status_effect {
+                status_effects.apply(status_effect);
+            }
+            let original_color = sprite.color;
+            commands
+                .entity(event.enemy_entity)
+                .entry::<HitFlash>()
+                .and_modify(|mut flash| flash.restart())
+                .or_insert(HitFlash::new(original_color));
+            if health.current <= 0.0 {
+                *state =
+                    CharacterStateMode::one_shot(CharacterState::Death, false, OnOneShotEnd::Die);
+                // Dead enemies stop where they are, rather than drifting off with the knockback
+                commands
+                    .entity(event.enemy_entity)
+                    .insert(Death::Dying)
+                    .remove::<(Collider, body::Knockback)>();
+            } else {
+                knockback.apply(event.knockback, &mut linear_velocity);
+            }
+        }
+    }
+}
+
+type LivingEnemy = (With<Enemy>, Without<Death>);
+
+fn status_effect_damage(
+    mut enemy_query: Query<(Entity, &mut StatusEffects), LivingEnemy>,
+    mut enemy_hits: MessageWriter<EnemyHit>,
+) {
+    for (enemy_entity, mut status_effects) in enemy_query.iter_mut() {
+        // Pending damage is bookkeeping, so draining it shouldn't count as a change to the effects
+        if let Some(damage) = status_effects
+            .bypass_change_detection()
+            .take_pending_damage()
+        {
+            enemy_hits.write(EnemyHit {
+                enemy_entity,
+                damage,
+                is_crit: false,
+                status_effect: None,
+                knockback: Vec2::ZERO,
+            });
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use super::{Enemy, archetype::RangedConfig, flow_field::FlowField};
+use crate::{
+    GameState,
+    core::{body::MovementConfig, components::Death, status_effects::StatusEffects},
+    loading::TextureAssets,
+    player::Player,
+    player_levelup::not_leveling_up,
+    projectile::{self, ProjectileBehaviour, ProjectileStats, Team},
+};
+
+pub struct RangedEnemyPlugin;
+
+impl Plugin for RangedEnemyPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<RangedAttacker>().add_systems(
+            Update,
+            ranged_enemy_behaviour.run_if(in_state(GameState::Playing).and(not_leveling_up)),
+        );
+    }
+}
+
+#[derive(Reflect, Debug)]
+enum RangedState {
+    Approaching,
+    Aiming(Timer),
+    Cooldown(Timer),
+}
+
+/// Enemies that keep their distance and fire projectiles at the player
+#[derive(Component, Reflect)]
+pub struct RangedAttacker {
+    config: RangedConfig,
+    state: RangedState,
+}
+
+impl RangedAttacker {
+    pub fn new(config: RangedConfig) -> Self {
+        RangedAttacker {
+            config,
+            state: RangedState::Approaching,
+        }
+    }
+}
+
+type RangedEnemyData<'a> = (
+    &'a mut RangedAttacker,
+    &'a mut LinearVelocity,
+    &'a Transform,
+    &'a MovementConfig,
+    &'a StatusEffects,
+    Has<Death>,
+);
+
+fn ranged_enemy_behaviour(
+    mut commands: Commands,
+    time: Res<Time>,
+    flow_field: Res<FlowField>,
+    textures: Res<TextureAssets>,
+    player_transform: Single<&Transform, (With<Player>, Without<Enemy>)>,
+    mut enemy_query: Query<RangedEnemyData, With<Enemy>>,
+) {
+    let player_position = player_transform.translation.truncate();
+    for (mut attacker, mut linear_velocity, transform, movement_config, status_effects, is_dying) in
+        enemy_query.iter_mut()
+    {
+        if status_effects.is_stunned() {
+            continue;
+        }
+        if is_dying {
+            linear_velocity.0 = Vec2::ZERO;
+            continue;
+        }
+        let position = transform.translation.truncate();
+        let to_player = player_position - position;
+        let distance = to_player.length();
+        let acceleration = time.delta_secs() * movement_config.acceleration;
+        let approach = flow_field
+            .direction_at(position)
+            .unwrap_or_else(|| to_player.normalize_or_zero());
+        let config = attacker.config;
+
+        match &mut attacker.state {
+            RangedState::Approaching => {
+                if distance <= config.fire_range {
+                    attacker.state = RangedState::Aiming(Timer::from_seconds(
+                        config.aim_time_s,
+                        TimerMode::Once,
+                    ));
+                } else {
+                    linear_velocity.0 += approach * acceleration;
+                }
+            }
+            RangedState::Aiming(timer) => {
+                // Stand still while aiming so the shot is telegraphed
+                linear_velocity.0 = linear_velocity.0.move_towards(Vec2::ZERO, acceleration);
+                timer.tick(time.delta());
+                if distance > config.fire_range * 1.2 {
+                    attacker.state = RangedState::Approaching;
+                } else if timer.is_finished() {
+                    projectile::spawn_projectile(
+                        &mut commands,
+                        transform.translation,
+                        to_player.normalize_or_zero(),
+                        ProjectileStats {
+                            speed: config.projectile_speed,
+                            damage: config.projectile_damage,
+                            crit_chance: 0.0,
+                            crit_multiplier: 1.0,
+                            pierce: 1,
+                            bounces: 0,
+                            team: Team::Enemy,
+                            status_effect: config.projectile_status_effect,
+                            behaviour: ProjectileBehaviour::Straight,
+                            explosive: None,
+                            chain: None,
+                            range: projectile::ENEMY_PROJECTILE_RANGE,
+                            size: 1.0,
+                            knockback: 0.0,
+                        },
+                        &textures,
+                    );
+                    attacker.state = RangedState::Cooldown(Timer::from_seconds(
+                        config.cooldown_s,
+                        TimerMode::Once,
+                    ));
+                }
+            }
+            RangedState::Cooldown(timer) => {
+                timer.tick(time.delta());
+                if distance < config.keep_distance {
+                    linear_velocity.0 -= to_player.normalize_or_zero() * acceleration;
+                } else if distance > config.fire_range {
+                    linear_velocity.0 += approach * acceleration;
+                } else {
+                    linear_velocity.0 = linear_velocity.0.move_towards(Vec2::ZERO, acceleration);
+                }
+                if timer.is_finished() {
+                    attacker.state = RangedState::Approaching;
+                }
+            }
+        }
+    }
+}
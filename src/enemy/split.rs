@@ -0,0 +1,63 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::SpawnEnemy;
+use crate::core::components::DeathEvent;
+
+pub struct SplitPlugin;
+
+impl Plugin for SplitPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SplitOnDeath>()
+            .add_observer(split_on_death);
+    }
+}
+
+/// Splits an enemy into smaller copies of itself when it dies
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Reflect)]
+pub struct SplitDefinition {
+    pub count: u32,
+    /// Size of each child relative to its parent. Health, collider and sprite all scale by this.
+    pub size: f32,
+    /// How many times the split can repeat, e.g. 2 means children split once more
+    #[serde(default = "default_generations")]
+    pub generations: u32,
+}
+
+fn default_generations() -> u32 {
+    1
+}
+
+/// Spawns more enemies in its place when this dies
+#[derive(Component, Reflect)]
+pub struct SplitOnDeath {
+    pub archetype: String,
+    pub count: u32,
+    /// Size of the spawned enemies, relative to their archetype
+    pub size: f32,
+    /// Generation of the spawned enemies
+    pub generation: u32,
+}
+
+fn split_on_death(
+    death_event: On<DeathEvent>,
+    splitters: Query<&SplitOnDeath>,
+    mut spawn_enemies: MessageWriter<SpawnEnemy>,
+) {
+    let Ok(split) = splitters.get(death_event.entity) else {
+        return;
+    };
+    let position = death_event.position.truncate();
+    for i in 0..split.count {
+        let angle = TAU * i as f32 / split.count as f32;
+        spawn_enemies.write(SpawnEnemy {
+            global_position: position + Vec2::from_angle(angle) * 6.0 * split.size,
+            archetype: split.archetype.clone(),
+            affixes: Vec::new(),
+            size: split.size,
+            generation: split.generation,
+        });
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use super::LivingEnemy;
+use crate::GameState;
+
+pub struct SteeringPlugin;
+
+impl Plugin for SteeringPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SteeringConfig>()
+            .init_resource::<EnemySpatialHash>()
+            .register_type::<SteeringConfig>()
+            .add_systems(
+                Update,
+                update_spatial_hash
+                    .before(super::move_enemy)
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+/// Weights for the crowd steering layered on top of the flow field
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub struct SteeringConfig {
+    /// Push away from neighbours closer than this
+    pub separation_radius: f32,
+    pub separation_weight: f32,
+    /// Drift towards the centre of neighbours within this
+    pub cohesion_radius: f32,
+    pub cohesion_weight: f32,
+}
+
+impl Default for SteeringConfig {
+    fn default() -> Self {
+        SteeringConfig {
+            separation_radius: 10.0,
+            separation_weight: 1.5,
+            cohesion_radius: 24.0,
+            cohesion_weight: 0.2,
+        }
+    }
+}
+
+const CELL_SIZE: f32 = 16.0;
+
+/// Living enemy positions bucketed into a grid, so neighbours can be found without checking
+/// every other enemy
+#[derive(Resource, Default)]
+pub struct EnemySpatialHash {
+    cells: HashMap<IVec2, Vec<(Entity, Vec2)>>,
+}
+
+impl EnemySpatialHash {
+    fn cell(position: Vec2) -> IVec2 {
+        (position / CELL_SIZE).floor().as_ivec2()
+    }
+
+    fn clear(&mut self) {
+        // Keep the allocations around, the same cells tend to be reused next frame
+        for entities in self.cells.values_mut() {
+            entities.clear();
+        }
+    }
+
+    pub(crate) fn insert(&mut self, entity: Entity, position: Vec2) {
+        self.cells
+            .entry(Self::cell(position))
+            .or_default()
+            .push((entity, position));
+    }
+
+    /// Other entities within `radius` of `position`, with their positions
+    pub fn neighbours(
+        &self,
+        entity: Entity,
+        position: Vec2,
+        radius: f32,
+    ) -> impl Iterator<Item = (Entity, Vec2)> + '_ {
+        let min = Self::cell(position - radius);
+        let max = Self::cell(position + radius);
+        (min.x..=max.x)
+            .flat_map(move |x| (min.y..=max.y).map(move |y| IVec2::new(x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+            .filter(move |(other, other_position)| {
+                *other != entity && other_position.distance_squared(position) <= radius * radius
+            })
+    }
+
+    /// Separation and cohesion steering for the entity at `position`
+    pub fn steering(&self, entity: Entity, position: Vec2, config: &SteeringConfig) -> Vec2 {
+        let mut separation = Vec2::ZERO;
+        for (_, other_position) in self.neighbours(entity, position, config.separation_radius) {
+            let offset = position - other_position;
+            let distance = offset.length();
+            // Stacked exactly on top of each other has no direction to push in, leave it to
+            // the physics to pull them apart
+            if distance > 0.0 {
+                separation += offset / distance * (1.0 - distance / config.separation_radius);
+            }
+        }
+        let (sum, count) = self
+            .neighbours(entity, position, config.cohesion_radius)
+            .fold((Vec2::ZERO, 0), |(sum, count), (_, other_position)| {
+                (sum + other_position, count + 1)
+            });
+        let cohesion = if count > 0 {
+            (sum / count as f32 - position).normalize_or_zero()
+        } else {
+            Vec2::ZERO
+        };
+        separation * config.separation_weight + cohesion * config.cohesion_weight
+    }
+}
+
+fn update_spatial_hash(
+    mut spatial_hash: ResMut<EnemySpatialHash>,
+    enemies: Query<(Entity, &Transform), LivingEnemy>,
+) {
+    spatial_hash.clear();
+    for (entity, transform) in enemies.iter() {
+        spatial_hash.insert(entity, transform.translation.truncate());
+    }
+}
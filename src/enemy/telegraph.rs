@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+
+use super::SpawnEnemy;
+use crate::{
+    GameState, camera::GAME_RENDER_LAYER, loading::TextureAssets, player_levelup::not_leveling_up,
+};
+
+pub struct TelegraphPlugin;
+
+impl Plugin for TelegraphPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<TelegraphSpawn>()
+            .register_type::<SpawnTelegraph>()
+            .add_systems(
+                Update,
+                (
+                    spawn_telegraphs.run_if(in_state(GameState::Playing)),
+                    tick_telegraphs.run_if(in_state(GameState::Playing).and(not_leveling_up)),
+                ),
+            );
+    }
+}
+
+/// Spawn an enemy after showing a marker on the ground for `delay_s`
+#[derive(Message, Debug)]
+pub(super) struct TelegraphSpawn {
+    pub spawn: SpawnEnemy,
+    pub delay_s: f32,
+}
+
+/// Ground marker for an enemy that's about to spawn
+#[derive(Component, Reflect)]
+pub(super) struct SpawnTelegraph {
+    spawn: SpawnEnemy,
+    timer: Timer,
+}
+
+/// Just under enemies, so the enemy covers the marker the moment it appears
+const TELEGRAPH_Z: f32 = super::ENEMY_Z - 1.0;
+
+fn spawn_telegraphs(
+    mut commands: Commands,
+    mut events: MessageReader<TelegraphSpawn>,
+    textures: Res<TextureAssets>,
+) {
+    for TelegraphSpawn { spawn, delay_s } in events.read() {
+        commands.spawn((
+            Name::new("Spawn Telegraph"),
+            Transform::from_translation(spawn.global_position.extend(TELEGRAPH_Z))
+                .with_scale(Vec3::splat(0.5 * spawn.size)),
+            Sprite {
+                color: Color::srgba(1.0, 0.3, 0.3, 0.0),
+                ..Sprite::from_image(textures.cursor_crosshair.clone())
+            },
+            SpawnTelegraph {
+                spawn: spawn.clone(),
+                timer: Timer::from_seconds(*delay_s, TimerMode::Once),
+            },
+            GAME_RENDER_LAYER,
+        ));
+    }
+}
+
+fn tick_telegraphs(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut telegraphs: Query<(Entity, &mut SpawnTelegraph, &mut Sprite, &mut Transform)>,
+    mut spawn_enemies: MessageWriter<SpawnEnemy>,
+) {
+    for (entity, mut telegraph, mut sprite, mut transform) in telegraphs.iter_mut() {
+        telegraph.timer.tick(time.delta());
+        if telegraph.timer.is_finished() {
+            spawn_enemies.write(telegraph.spawn.clone());
+            commands.entity(entity).despawn();
+            continue;
+        }
+        // Fade in and spin faster as the spawn gets closer
+        let progress = telegraph.timer.fraction();
+        sprite.color.set_alpha(progress.sqrt() * 0.8);
+        transform.rotate_z(time.delta_secs() * (1.0 + progress * 6.0));
+    }
+}
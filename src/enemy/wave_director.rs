@@ -0,0 +1,323 @@
+use std::{collections::HashSet, time::Duration};
+
+use bevy::prelude::*;
+use bevy_ecs_ldtk::GridCoords;
+use bevy_prng::WyRand;
+use bevy_rand::global::GlobalRng;
+use rand::{
+    RngExt,
+    seq::{IndexedRandom, IteratorRandom},
+};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    Enemy, SpawnEnemy,
+    affixes::Affix,
+    telegraph::{SpawnTelegraph, TelegraphSpawn},
+};
+use crate::{GameState, level_loader::Floor, player::Player, player_levelup::not_leveling_up};
+
+pub struct WaveDirectorPlugin;
+
+impl Plugin for WaveDirectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WaveDirector>()
+            .register_type::<WaveDirector>()
+            .add_systems(OnEnter(GameState::Playing), WaveDirector::reset)
+            .add_systems(
+                Update,
+                WaveDirector::system.run_if(in_state(GameState::Playing).and(not_leveling_up)),
+            );
+    }
+}
+
+/// A value that changes linearly from `start` to `end` over the course of a wave window
+#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+pub struct Ramp {
+    pub start: f32,
+    pub end: f32,
+}
+
+impl Ramp {
+    pub fn at(&self, t: f32) -> f32 {
+        self.start.lerp(self.end, t.clamp(0.0, 1.0))
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+pub enum SpawnPattern {
+    /// Spawn on random floor tiles away from the player
+    Scattered { count: usize },
+    /// Spawn a group clustered around a single random floor tile
+    Burst { count: usize, spread: f32 },
+    /// Spawn evenly spaced in a circle around the player
+    Ring { count: usize, radius: f32 },
+}
+
+impl SpawnPattern {
+    fn count(&self) -> usize {
+        match self {
+            SpawnPattern::Scattered { count }
+            | SpawnPattern::Burst { count, .. }
+            | SpawnPattern::Ring { count, .. } => *count,
+        }
+    }
+}
+
+/// One stage of the wave script, active from `start_s` until the next window starts
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct WaveWindow {
+    pub start_s: f32,
+    /// Length of the window used to progress the ramps. The final window holds its end values.
+    pub duration_s: f32,
+    pub spawn_interval_s: Ramp,
+    pub max_enemies: usize,
+    /// Archetype ids and their relative spawn weights
+    pub archetypes: Vec<(String, f32)>,
+    pub pattern: SpawnPattern,
+    /// Chance for each spawned enemy to be an elite with random affixes
+    #[serde(default)]
+    pub elite_chance: f32,
+    #[serde(default)]
+    pub max_affixes: usize,
+}
+
+/// A boss spawned once the run reaches `at_s`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct BossSpawn {
+    pub at_s: f32,
+    pub archetype: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Asset, TypePath)]
+pub struct WaveScript {
+    /// Enemies never spawn closer than this to the player
+    pub min_player_distance: f32,
+    pub windows: Vec<WaveWindow>,
+    /// Bosses in the order they appear
+    #[serde(default)]
+    pub bosses: Vec<BossSpawn>,
+    /// How long the ground marker shows before each enemy appears
+    #[serde(default = "default_telegraph_s")]
+    pub telegraph_s: f32,
+}
+
+fn default_telegraph_s() -> f32 {
+    1.0
+}
+
+impl WaveScript {
+    /// The active window at the given run time and how far through it we are, from 0 to 1
+    pub fn window_at(&self, elapsed_s: f32) -> Option<(&WaveWindow, f32)> {
+        self.windows
+            .iter()
+            .rev()
+            .find(|window| window.start_s <= elapsed_s)
+            .map(|window| {
+                let progress = (elapsed_s - window.start_s) / window.duration_s.max(f32::EPSILON);
+                (window, progress.clamp(0.0, 1.0))
+            })
+    }
+}
+
+/// A random floor tile position at least `min_distance` from the player
+fn random_far_floor(
+    floors: &HashSet<GridCoords>,
+    player_position: Vec2,
+    min_distance: f32,
+    rng: &mut WyRand,
+) -> Option<Vec2> {
+    floors
+        .iter()
+        .map(|floor| bevy_ecs_ldtk::utils::grid_coords_to_translation(*floor, IVec2::splat(8)))
+        .filter(|position| position.distance(player_position) > min_distance)
+        .choose(rng)
+}
+
+#[derive(Resource, Reflect)]
+pub struct WaveDirector {
+    pub elapsed: Duration,
+    spawn_timer: Timer,
+    next_boss: usize,
+}
+
+impl Default for WaveDirector {
+    fn default() -> Self {
+        WaveDirector {
+            elapsed: Duration::ZERO,
+            spawn_timer: Timer::from_seconds(0.0, TimerMode::Once),
+            next_boss: 0,
+        }
+    }
+}
+
+/// Pending spawns count towards the enemy cap too
+type EnemyOrPending = Or<(With<Enemy>, With<SpawnTelegraph>)>;
+
+impl WaveDirector {
+    fn reset(mut director: ResMut<Self>) {
+        *director = WaveDirector::default();
+    }
+
+    #[expect(clippy::too_many_arguments)]
+    fn system(
+        time: Res<Time>,
+        mut director: ResMut<Self>,
+        wave_scripts: Res<Assets<WaveScript>>,
+        custom_assets: Res<crate::loading::CustomAssets>,
+        mut event_writer: MessageWriter<TelegraphSpawn>,
+        floor_query: Query<&GridCoords, With<Floor>>,
+        player_transform: Single<&Transform, With<Player>>,
+        enemies: Query<(), EnemyOrPending>,
+        mut rng: Single<&mut WyRand, With<GlobalRng>>,
+    ) -> Result {
+        director.elapsed += time.delta();
+        director.spawn_timer.tick(time.delta());
+        let script = wave_scripts
+            .get(&custom_assets.wave_script)
+            .ok_or_else(|| anyhow::anyhow!("Wave script not loaded"))?;
+        let player_position = player_transform.translation.truncate();
+
+        if let Some(boss) = script.bosses.get(director.next_boss)
+            && director.elapsed.as_secs_f32() >= boss.at_s
+        {
+            let floors: HashSet<GridCoords> = floor_query.iter().copied().collect();
+            if let Some(global_position) = random_far_floor(
+                &floors,
+                player_position,
+                script.min_player_distance,
+                &mut rng,
+            ) {
+                info!("Spawning boss {}", boss.archetype);
+                event_writer.write(TelegraphSpawn {
+                    spawn: SpawnEnemy {
+                        global_position,
+                        archetype: boss.archetype.clone(),
+                        affixes: Vec::new(),
+                        size: 1.0,
+                        generation: 0,
+                    },
+                    delay_s: script.telegraph_s,
+                });
+                director.next_boss += 1;
+            }
+        }
+
+        if !director.spawn_timer.is_finished() {
+            return Ok(());
+        }
+        let Some((window, progress)) = script.window_at(director.elapsed.as_secs_f32()) else {
+            return Ok(());
+        };
+        director.spawn_timer = Timer::from_seconds(
+            window.spawn_interval_s.at(progress).max(0.01),
+            TimerMode::Once,
+        );
+
+        let num_enemies = enemies.iter().len();
+        let budget = window.max_enemies.saturating_sub(num_enemies);
+        if budget == 0 {
+            info!("Too many enemies, not spawning ({})", num_enemies);
+            return Ok(());
+        }
+        let count = window.pattern.count().min(budget);
+        let floors: HashSet<GridCoords> = floor_query.iter().copied().collect();
+        let is_valid = |position: Vec2| {
+            floors.contains(&bevy_ecs_ldtk::utils::translation_to_grid_coords(
+                position,
+                IVec2::splat(8),
+            )) && position.distance(player_position) > script.min_player_distance
+        };
+        let random_far_floor = |rng: &mut WyRand| {
+            random_far_floor(&floors, player_position, script.min_player_distance, rng)
+        };
+
+        let positions: Vec<Vec2> = match window.pattern {
+            SpawnPattern::Scattered { .. } => (0..count)
+                .filter_map(|_| random_far_floor(&mut rng))
+                .collect(),
+            SpawnPattern::Burst { spread, .. } => match random_far_floor(&mut rng) {
+                Some(centre) => (0..count)
+                    .map(|_| {
+                        let offset = Vec2::new(
+                            rng.random_range(-spread..=spread),
+                            rng.random_range(-spread..=spread),
+                        );
+                        centre + offset
+                    })
+                    .filter(|position| is_valid(*position))
+                    .collect(),
+                None => Vec::new(),
+            },
+            SpawnPattern::Ring { radius, .. } => {
+                let start_angle = rng.random_range(0.0..std::f32::consts::TAU);
+                (0..count)
+                    .map(|i| {
+                        let angle = start_angle + std::f32::consts::TAU * i as f32 / count as f32;
+                        player_position + Vec2::from_angle(angle) * radius
+                    })
+                    .filter(|position| is_valid(*position))
+                    .collect()
+            }
+        };
+
+        for global_position in positions {
+            let Ok((archetype, _weight)) = window
+                .archetypes
+                .choose_weighted(&mut rng, |(_archetype, weight)| *weight)
+            else {
+                warn!("Wave window at {}s has no valid archetypes", window.start_s);
+                break;
+            };
+            let affixes = Affix::roll(&mut rng, window.elite_chance, window.max_affixes);
+            event_writer.write(TelegraphSpawn {
+                spawn: SpawnEnemy {
+                    global_position,
+                    archetype: archetype.clone(),
+                    affixes,
+                    size: 1.0,
+                    generation: 0,
+                },
+                delay_s: script.telegraph_s,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn spawn_rate_rises_across_window_boundaries() {
+    let file = std::fs::read_to_string("assets/custom_assets.asset.json").unwrap();
+    let assets: serde_json::Value = serde_json::from_str(&file).unwrap();
+    let script: WaveScript =
+        serde_json::from_value(assets["wave_script"]["WaveScript"].clone()).unwrap();
+    let spawn_rate = |elapsed_s: f32| {
+        let (window, progress) = script.window_at(elapsed_s).unwrap();
+        window.pattern.count() as f32 / window.spawn_interval_s.at(progress)
+    };
+    for pair in script.windows.windows(2) {
+        let (previous, next) = (&pair[0], &pair[1]);
+        let boundary = next.start_s;
+        // The new window starts from its own start values right at the boundary
+        let (window, progress) = script.window_at(boundary).unwrap();
+        assert_eq!(window.start_s, next.start_s);
+        assert_eq!(progress, 0.0);
+        assert_eq!(
+            window.spawn_interval_s.at(progress),
+            next.spawn_interval_s.start
+        );
+        // Just before it, the previous window is still active
+        let (window, _) = script.window_at(boundary - 0.01).unwrap();
+        assert_eq!(window.start_s, previous.start_s);
+        assert!(
+            spawn_rate(boundary) >= spawn_rate(boundary - 0.01),
+            "Spawn rate drops at {boundary}s"
+        );
+    }
+    // The final window holds its end values
+    let last = script.windows.last().unwrap();
+    let (_, progress) = script
+        .window_at(last.start_s + last.duration_s * 10.0)
+        .unwrap();
+    assert_eq!(progress, 1.0);
+}
@@ -27,6 +27,16 @@ impl Action {
         }
     }
 
+    /// Each skill slot's action, with the slot number
+    pub fn attack_slots() -> [(u8, Action); 4] {
+        [
+            (1, Action::AttackSlot1),
+            (2, Action::AttackSlot2),
+            (3, Action::AttackSlot3),
+            (4, Action::AttackSlot4),
+        ]
+    }
+
     pub fn all_movements() -> [Action; 4] {
         [
             Action::MoveUp,
@@ -1,6 +1,14 @@
 use std::collections::HashMap;
 
-use crate::{GameState, core::directional_animation::DirectionalAnimationAsset};
+use crate::{
+    GameState,
+    core::directional_animation::DirectionalAnimationAsset,
+    enemy::{
+        archetype::{AnimationRef, EnemyArchetypeDefinition, EnemyArchetypeLibrary},
+        wave_director::WaveScript,
+    },
+    player_skills::{SkillDefinition, SkillLibrary},
+};
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -24,6 +32,9 @@ impl Plugin for LoadingPlugin {
                     "custom_assets.asset.json",
                 ),
         )
+        .init_asset::<EnemyArchetypeLibrary>()
+        .init_asset::<WaveScript>()
+        .init_asset::<SkillLibrary>()
         .add_plugins(bevy_common_assets::ron::RonAssetPlugin::<
             CustomDynamicAssetCollection,
         >::new(&["asset.ron"]))
@@ -72,6 +83,12 @@ pub struct CustomAssets {
     pub player_animation: Handle<DirectionalAnimationAsset>,
     #[asset(key = "slime")]
     pub slime_animation: Handle<DirectionalAnimationAsset>,
+    #[asset(key = "enemy_archetypes")]
+    pub enemy_archetypes: Handle<EnemyArchetypeLibrary>,
+    #[asset(key = "wave_script")]
+    pub wave_script: Handle<WaveScript>,
+    #[asset(key = "skills")]
+    pub skills: Handle<SkillLibrary>,
 }
 
 #[derive(AssetCollection, Resource)]
@@ -80,10 +97,6 @@ pub struct TextureAssets {
     pub player_sheet_idle: Handle<Image>,
     #[asset(path = "textures/Ranger_walk.png")]
     pub player_sheet_walk: Handle<Image>,
-    #[asset(path = "textures/SlimeGreenIdle.png")]
-    pub slime: Handle<Image>,
-    #[asset(path = "textures/SlimeGreenDie.png")]
-    pub slime_death: Handle<Image>,
     #[asset(path = "textures/target_b.png")]
     pub cursor_crosshair: Handle<Image>,
     #[asset(path = "textures/arrow_single_right.png")]
@@ -117,16 +130,49 @@ pub struct UiTextureAssets {
 #[derive(Deserialize, Serialize, Debug, Clone)]
 enum CustomAssetLoader {
     DirectionalAnimation(DirectionalAnimationAsset),
+    EnemyArchetypes(HashMap<String, EnemyArchetypeDefinition>),
+    WaveScript(WaveScript),
+    Skills(HashMap<String, SkillDefinition>),
 }
 
-impl DynamicAsset for CustomAssetLoader {
-    fn load(&self, _asset_server: &AssetServer) -> Vec<UntypedHandle> {
+impl CustomAssetLoader {
+    /// Replaces references to other keys in the same file with the assets they point to
+    fn resolve(&self, all: &HashMap<String, CustomAssetLoader>) -> Self {
         match self {
-            CustomAssetLoader::DirectionalAnimation(_asset) => {
-                vec![]
+            CustomAssetLoader::DirectionalAnimation(_)
+            | CustomAssetLoader::WaveScript(_)
+            | CustomAssetLoader::Skills(_) => self.clone(),
+            CustomAssetLoader::EnemyArchetypes(definitions) => {
+                let mut definitions = definitions.clone();
+                for (id, definition) in definitions.iter_mut() {
+                    if let AnimationRef::Key(key) = &definition.animation {
+                        match all.get(key) {
+                            Some(CustomAssetLoader::DirectionalAnimation(animation)) => {
+                                definition.animation = AnimationRef::Inline(animation.clone());
+                            }
+                            _ => error!("Enemy archetype {id} references unknown animation {key}"),
+                        }
+                    }
+                }
+                CustomAssetLoader::EnemyArchetypes(definitions)
             }
         }
     }
+}
+
+impl DynamicAsset for CustomAssetLoader {
+    fn load(&self, asset_server: &AssetServer) -> Vec<UntypedHandle> {
+        match self {
+            CustomAssetLoader::DirectionalAnimation(_)
+            | CustomAssetLoader::WaveScript(_)
+            | CustomAssetLoader::Skills(_) => vec![],
+            CustomAssetLoader::EnemyArchetypes(definitions) => definitions
+                .values()
+                .flat_map(|definition| definition.texture_paths())
+                .map(|path| asset_server.load::<Image>(path).untyped())
+                .collect(),
+        }
+    }
 
     fn build(&self, world: &mut World) -> Result<DynamicAssetType, anyhow::Error> {
         match self {
@@ -135,6 +181,33 @@ impl DynamicAsset for CustomAssetLoader {
                 let handle = das.add(asset.clone()).untyped();
                 Ok(DynamicAssetType::Single(handle))
             }
+            CustomAssetLoader::EnemyArchetypes(definitions) => {
+                let asset_server = world.resource::<AssetServer>().clone();
+                let mut animations =
+                    world.get_resource_or_init::<Assets<DirectionalAnimationAsset>>();
+                let archetypes = definitions
+                    .iter()
+                    .map(|(id, definition)| {
+                        Ok((
+                            id.clone(),
+                            definition.build(&asset_server, &mut animations)?,
+                        ))
+                    })
+                    .collect::<Result<HashMap<_, _>, anyhow::Error>>()?;
+                let mut libraries = world.get_resource_or_init::<Assets<EnemyArchetypeLibrary>>();
+                let handle = libraries.add(EnemyArchetypeLibrary(archetypes)).untyped();
+                Ok(DynamicAssetType::Single(handle))
+            }
+            CustomAssetLoader::WaveScript(script) => {
+                let mut scripts = world.get_resource_or_init::<Assets<WaveScript>>();
+                let handle = scripts.add(script.clone()).untyped();
+                Ok(DynamicAssetType::Single(handle))
+            }
+            CustomAssetLoader::Skills(definitions) => {
+                let mut libraries = world.get_resource_or_init::<Assets<SkillLibrary>>();
+                let handle = libraries.add(SkillLibrary(definitions.clone())).untyped();
+                Ok(DynamicAssetType::Single(handle))
+            }
         }
     }
 }
@@ -145,7 +218,7 @@ pub struct CustomDynamicAssetCollection(HashMap<String, CustomAssetLoader>);
 impl DynamicAssetCollection for CustomDynamicAssetCollection {
     fn register(&self, dynamic_assets: &mut DynamicAssets) {
         for (key, asset) in self.0.iter() {
-            dynamic_assets.register_asset(key, Box::new(asset.clone()));
+            dynamic_assets.register_asset(key, Box::new(asset.resolve(&self.0)));
         }
     }
 }
@@ -199,3 +272,41 @@ fn serilising() {
     }
     "#);
 }
+
+#[test]
+fn resolving_animation_keys() {
+    let assets: HashMap<String, CustomAssetLoader> = serde_json::from_str(
+        r#"{
+            "slime": { "DirectionalAnimation": { "Walking": { "row_length": 4, "frame_duration": 0.3 } } },
+            "enemy_archetypes": { "EnemyArchetypes": { "slime": {
+                "health": 10.0,
+                "collider": { "Circle": { "radius": 3.0 } },
+                "movement": { "max_speed": 50.0, "acceleration": 100.0 },
+                "contact_damage": 1.0,
+                "xp_value": 10.0,
+                "animation": "slime",
+                "textures": { "Walking": "textures/SlimeGreenIdle.png" }
+            } } }
+        }"#,
+    )
+    .unwrap();
+    let CustomAssetLoader::EnemyArchetypes(definitions) =
+        assets["enemy_archetypes"].resolve(&assets)
+    else {
+        panic!("Expected enemy archetypes");
+    };
+    assert!(matches!(
+        definitions["slime"].animation,
+        AnimationRef::Inline(_)
+    ));
+}
+
+#[test]
+fn parsing_custom_assets_file() {
+    let file = std::fs::read_to_string("assets/custom_assets.asset.json").unwrap();
+    let assets: HashMap<String, CustomAssetLoader> = serde_json::from_str(&file).unwrap();
+    let Some(CustomAssetLoader::Skills(skills)) = assets.get("skills") else {
+        panic!("Expected skills");
+    };
+    assert!(skills.contains_key("multi_shot"));
+}
@@ -15,7 +15,9 @@ mod camera;
 mod collisions;
 mod core;
 mod cursor;
+mod damage_numbers;
 mod drops;
+mod effects;
 mod enemy;
 mod input;
 mod level_loader;
@@ -55,6 +57,8 @@ impl Plugin for GamePlugin {
                 player_skills::PlayerSkillsPlugin,
                 drops::DropsPlugin,
                 uis::UisPlugin,
+                damage_numbers::DamageNumbersPlugin,
+                effects::EffectsPlugin,
             ))
             .add_plugins(
                 // N.b. This depends on the egui plugin that's auto-added by bevy_egui. If that is removed
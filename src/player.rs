@@ -15,12 +15,16 @@ use crate::{
             CharacterState, DirectionalAnimationAsset, SupportsVelocityStateTransition,
             directional_animation_bundle,
         },
+        status_effects::StatusEffects,
     },
+    damage_numbers::{DamageNumberStyle, ShowDamageNumber},
     input::Action,
     level_loader::SpawnPoint,
     loading::TextureAssets,
-    player_skills::{OptionOrLocked, Skill, SkillEffect, SkillSlots},
-    projectile::Quiver,
+    player_skills::{Aim, OptionOrLocked, SkillLibrary, SkillSlots},
+    projectile::{
+        Homing, PlayerHit, ProjectileBehaviour, ProjectileStats, Quiver, Team, chain::Chain,
+    },
 };
 
 pub struct PlayerPlugin;
@@ -31,6 +35,8 @@ pub struct Player;
 pub struct PlayerPickupSensor;
 
 pub const PLAYER_Z: f32 = 100.0;
+/// Id of the skill the player starts with in the first slot
+const STARTING_SKILL: &str = "multi_shot";
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
@@ -40,6 +46,8 @@ impl Plugin for PlayerPlugin {
                 move_player,
                 player_shoot,
                 collisions_with_player,
+                get_hit,
+                status_effect_damage,
                 player_skill_action,
             )
                 .run_if(in_state(GameState::Playing)),
@@ -50,12 +58,14 @@ impl Plugin for PlayerPlugin {
     }
 }
 
+#[expect(clippy::too_many_arguments)]
 fn spawn(
     add: On<Add, SpawnPoint>,
     mut commands: Commands,
     grid_coords: Query<&GridCoords>,
     textures: Res<crate::loading::TextureAssets>,
     custom_assets: Res<crate::loading::CustomAssets>,
+    skill_library: Res<SkillLibrary>,
     mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
     directional_animations: Res<Assets<DirectionalAnimationAsset>>,
 ) -> Result {
@@ -80,21 +90,7 @@ fn spawn(
             directional_animation_asset,
         )?
     };
-    let skill_slots = {
-        let mut cooldown_timer = Timer::from_seconds(1.0, TimerMode::Once);
-        //cooldown_timer.
-        SkillSlots {
-            skill1: OptionOrLocked::Some(Skill {
-                name: "Multi-Shot".to_string(),
-                description: "Shoot multiple arrows at once.".to_string(),
-                cooldown_timer,
-                effect: SkillEffect::ArrowVolley { arrow_count: 4 },
-            }),
-            skill2: OptionOrLocked::Locked,
-            skill3: OptionOrLocked::Locked,
-            skill4: OptionOrLocked::Locked,
-        }
-    };
+    let skill_slots = SkillSlots::new(skill_library.skill(STARTING_SKILL)?);
     let quiver = Quiver::new(
         player_params.quiver_size,
         Duration::from_secs_f32(player_params.quiver_reload_time_s),
@@ -112,6 +108,7 @@ fn spawn(
             Name::new("Player"),
             Player,
             Health::new(player_params.max_health),
+            StatusEffects::default(),
             crate::input::input_map(),
             Transform::from_translation(initial_translation),
             Collider::capsule(2.5, 5.0),
@@ -136,9 +133,17 @@ fn spawn(
 
 fn move_player(
     time: Res<Time>,
-    query: Single<(&ActionState<Action>, &mut LinearVelocity, &PlayerParameters), With<Player>>,
+    query: Single<
+        (
+            &ActionState<Action>,
+            &mut LinearVelocity,
+            &PlayerParameters,
+            &StatusEffects,
+        ),
+        With<Player>,
+    >,
 ) -> Result {
-    let (action_state, mut linear_velocity, player_params) = query.into_inner();
+    let (action_state, mut linear_velocity, player_params, status_effects) = query.into_inner();
 
     let mut direction_vec = Vec2::ZERO;
     for action in Action::all_movements() {
@@ -148,8 +153,10 @@ fn move_player(
             direction_vec += dir.as_vec2();
         }
     }
-    let movements =
-        direction_vec.normalize_or_zero() * time.delta_secs() * player_params.movement_speed;
+    let movements = direction_vec.normalize_or_zero()
+        * time.delta_secs()
+        * player_params.movement_speed
+        * status_effects.speed_multiplier();
     linear_velocity.0 = movements;
     Ok(())
 }
@@ -162,6 +169,7 @@ fn player_shoot(
             &Transform,
             &mut Quiver,
             &PlayerParameters,
+            &StatusEffects,
         ),
         With<Player>,
     >,
@@ -169,71 +177,118 @@ fn player_shoot(
     camera_query: Single<(&Camera, &GlobalTransform), With<MainCamera>>,
     textures: Res<TextureAssets>,
 ) -> Result {
-    let (action_state, transform, mut quiver, player_params) = player_query.into_inner();
-    let (camera, camera_transform) = *camera_query;
+    let (action_state, transform, mut quiver, player_params, status_effects) =
+        player_query.into_inner();
+    if status_effects.is_stunned() {
+        return Ok(());
+    }
     if action_state.just_pressed(&Action::MainAttack) && quiver.try_take() {
-        let target_screenspace = window.cursor_position().unwrap_or_default();
-        let target = camera.viewport_to_world_2d(camera_transform, target_screenspace)?;
-        let player_pos = transform.translation.truncate();
-        let direction = (target - player_pos).normalize_or_zero();
+        let Aim {
+            target, direction, ..
+        } = cursor_aim(transform, &window, *camera_query)?;
         println!("Player shoot action detected. target: {target:?}, direction: {direction:?}");
         crate::projectile::spawn_projectile(
             &mut commands,
             transform.translation,
             direction,
-            player_params.projectile_speed,
-            player_params.projectile_damage,
-            player_params.projectile_pierce.floor() as u32,
+            player_params.projectile_stats(),
             &textures,
         );
     }
     Ok(())
 }
 
+/// Aim from the player towards the cursor
+fn cursor_aim(
+    transform: &Transform,
+    window: &Window,
+    (camera, camera_transform): (&Camera, &GlobalTransform),
+) -> Result<Aim> {
+    let target_screenspace = window.cursor_position().unwrap_or_default();
+    let target = camera.viewport_to_world_2d(camera_transform, target_screenspace)?;
+    let origin = transform.translation.truncate();
+    Ok(Aim {
+        origin,
+        direction: (target - origin).normalize_or_zero(),
+        target,
+    })
+}
+
 fn player_skill_action(
     mut commands: Commands,
-    player_query: Single<(&ActionState<Action>, &Transform, &mut SkillSlots), With<Player>>,
-) {
-    let (action_state, _transform, mut skill_slots) = player_query.into_inner();
-    if action_state.just_pressed(&Action::AttackSlot1) {
-        match skill_slots.skill1.as_mut() {
-            OptionOrLocked::Some(skill) => {
-                if skill.maybe_trigger(&mut commands) {
-                    println!("Triggered skill 1: {}", skill.name);
+    player_query: Single<
+        (
+            &ActionState<Action>,
+            &Transform,
+            &mut SkillSlots,
+            &StatusEffects,
+        ),
+        With<Player>,
+    >,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera_query: Single<(&Camera, &GlobalTransform), With<MainCamera>>,
+) -> Result {
+    let (action_state, transform, mut skill_slots, status_effects) = player_query.into_inner();
+    if status_effects.is_stunned() {
+        return Ok(());
+    }
+    for (slot, action) in Action::attack_slots() {
+        if !action_state.just_pressed(&action) {
+            continue;
+        }
+        match skill_slots
+            .get_skill_slot_mut(slot)
+            .map(OptionOrLocked::as_mut)
+        {
+            Some(OptionOrLocked::Some(skill)) => {
+                let aim = cursor_aim(transform, &window, *camera_query)?;
+                if skill.maybe_trigger(&mut commands, aim) {
+                    println!("Triggered skill {slot}: {}", skill.name);
                 } else {
-                    println!("Can't trigger skill 1, on cooldown.");
+                    println!("Can't trigger skill {slot}, on cooldown.");
                 }
             }
-            OptionOrLocked::None => {
-                println!("Skill slot 1 is not set.");
+            Some(OptionOrLocked::None) => {
+                println!("Skill slot {slot} is not set.");
             }
-            OptionOrLocked::Locked => {
-                println!("Skill slot 1 is locked.");
+            Some(OptionOrLocked::Locked) | None => {
+                println!("Skill slot {slot} is locked.");
             }
         }
     }
+    Ok(())
 }
 
 fn collisions_with_player(
-    player_query: Single<(Entity, &mut Health), With<Player>>,
-    mut possible_colliders: Query<(&mut CollidesWithPlayer, Forces), Without<Player>>,
+    player: Single<Entity, With<Player>>,
+    mut possible_colliders: Query<
+        (&mut CollidesWithPlayer, Forces, Option<&StatusEffects>),
+        Without<Player>,
+    >,
     transforms_query: Query<&Transform>,
     collisions: Collisions,
     time: Res<Time>,
+    mut player_hits: MessageWriter<PlayerHit>,
 ) -> Result {
-    let (player, mut player_health) = player_query.into_inner();
+    let player = *player;
     let player_transform = transforms_query.get(player)?;
     for entity in collisions.entities_colliding_with(player) {
-        if let Ok((mut collides_with_player, mut forces)) = possible_colliders.get_mut(entity) {
+        if let Ok((mut collides_with_player, mut forces, status_effects)) =
+            possible_colliders.get_mut(entity)
+        {
             //Damage Player, if not collided recently
             let now = time.elapsed();
             let should_damage = match collides_with_player.last_collided {
                 Some(last_time) => (now - last_time) > collides_with_player.damage_cooldown,
                 None => true,
-            };
+            } && !status_effects.is_some_and(StatusEffects::is_stunned);
             if should_damage {
                 println!("Player collided with damaging entity!");
-                player_health.current -= collides_with_player.damage;
+                player_hits.write(PlayerHit {
+                    damage: collides_with_player.damage,
+                    source: Some(entity),
+                    status_effect: None,
+                });
                 collides_with_player.last_collided = Some(now);
             }
             let entity_transform = transforms_query.get(entity)?;
@@ -246,6 +301,42 @@ fn collisions_with_player(
     Ok(())
 }
 
+fn get_hit(
+    mut hit_events: MessageReader<PlayerHit>,
+    player: Single<(&mut Health, &mut StatusEffects, &Transform), With<Player>>,
+    mut damage_numbers: MessageWriter<ShowDamageNumber>,
+) {
+    let (mut health, mut status_effects, transform) = player.into_inner();
+    for event in hit_events.read() {
+        health.current -= event.damage;
+        damage_numbers.write(ShowDamageNumber {
+            position: transform.translation,
+            damage: event.damage,
+            style: DamageNumberStyle::Player,
+        });
+        if let Some(status_effect) = event.status_effect {
+            status_effects.apply(status_effect);
+        }
+    }
+}
+
+fn status_effect_damage(
+    mut player: Single<&mut StatusEffects, With<Player>>,
+    mut player_hits: MessageWriter<PlayerHit>,
+) {
+    if let Some(damage) = player.bypass_change_detection().take_pending_damage() {
+        player_hits.write(PlayerHit {
+            damage,
+            source: None,
+            status_effect: None,
+        });
+    }
+}
+
+pub const HOMING_ACQUISITION_RADIUS: f32 = 80.0;
+const CHAIN_RANGE: f32 = 60.0;
+const CHAIN_DAMAGE_DECAY: f32 = 0.7;
+
 #[derive(Debug, Clone, Copy, PartialEq, Reflect, Component)]
 pub struct PlayerParameters {
     pub movement_speed: f32,
@@ -253,8 +344,20 @@ pub struct PlayerParameters {
     pub projectile_speed: f32,
     // Pierce is a float so it can be incremented by fractions, but will be floored when applied.
     pub projectile_pierce: f32,
+    // Bounces off walls, floored like pierce.
+    pub projectile_bounces: f32,
+    // Jumps between enemies, floored like pierce.
+    pub projectile_chain: f32,
     pub projectile_size: f32,
+    /// Distance arrows fly before fading out
+    pub projectile_range: f32,
     pub projectile_damage: f32,
+    pub crit_chance: f32,
+    pub crit_multiplier: f32,
+    /// How hard arrows push enemies back
+    pub projectile_knockback: f32,
+    /// Turn rate of homing arrows in radians per second, 0 means arrows fly straight
+    pub projectile_homing: f32,
     pub quiver_size: usize,
     pub quiver_reload_time_s: f32,
 }
@@ -265,8 +368,15 @@ impl Default for PlayerParameters {
             movement_speed: 3000.0,
             projectile_speed: 70.0,
             projectile_pierce: 1.0,
+            projectile_bounces: 0.0,
+            projectile_chain: 0.0,
             projectile_size: 1.0,
+            projectile_range: 200.0,
             projectile_damage: 10.0,
+            crit_chance: 0.05,
+            crit_multiplier: 2.0,
+            projectile_knockback: 60.0,
+            projectile_homing: 0.0,
             quiver_size: 10,
             quiver_reload_time_s: 1.0,
             max_health: 10.0,
@@ -279,6 +389,40 @@ impl PlayerParameters {
         *self = PlayerParameters::default();
     }
 
+    pub fn projectile_stats(&self) -> ProjectileStats {
+        ProjectileStats {
+            speed: self.projectile_speed,
+            damage: self.projectile_damage,
+            crit_chance: self.crit_chance,
+            crit_multiplier: self.crit_multiplier,
+            pierce: self.projectile_pierce.floor() as u32,
+            bounces: self.projectile_bounces.floor() as u32,
+            team: Team::Player,
+            status_effect: None,
+            behaviour: if self.projectile_homing > 0.0 {
+                ProjectileBehaviour::Homing(Homing {
+                    turn_rate: self.projectile_homing,
+                    acquisition_radius: HOMING_ACQUISITION_RADIUS,
+                })
+            } else {
+                ProjectileBehaviour::Straight
+            },
+            explosive: None,
+            chain: if self.projectile_chain >= 1.0 {
+                Some(Chain {
+                    jumps: self.projectile_chain.floor() as u32,
+                    range: CHAIN_RANGE,
+                    damage_decay: CHAIN_DAMAGE_DECAY,
+                })
+            } else {
+                None
+            },
+            range: self.projectile_range,
+            size: self.projectile_size,
+            knockback: self.projectile_knockback,
+        }
+    }
+
     fn system(
         query: Single<(&Self, &mut Quiver, &mut Health), (With<Player>, Changed<PlayerParameters>)>,
     ) -> Result {
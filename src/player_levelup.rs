@@ -2,15 +2,20 @@ use avian2d::prelude::{Physics, PhysicsTime};
 use bevy::prelude::*;
 use bevy_prng::WyRand;
 use bevy_rand::global::GlobalRng;
-use rand::distr::Distribution;
+use rand::{distr::Distribution, seq::IndexedRandom};
 
-use crate::uis::level_up_cards::DisplayLevelUpCards;
+use crate::{
+    player::Player,
+    player_skills::{LearnSkill, SkillLibrary, SkillSlots, UnlockSkillSlot},
+    uis::level_up_cards::DisplayLevelUpCards,
+};
 
 pub struct PlayerLevelupPlugin;
 
 impl Plugin for PlayerLevelupPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(LevelingUp(false))
+            .init_resource::<PendingLevelUps>()
             .add_message::<LeveledUp>()
             .add_systems(
                 Update,
@@ -26,6 +31,12 @@ impl Plugin for PlayerLevelupPlugin {
 #[derive(Resource, Reflect, Default, Deref, DerefMut)]
 pub struct LevelingUp(pub bool);
 
+/// Run condition for gameplay timers, which should stand still while the cards are shown like
+/// physics does
+pub fn not_leveling_up(leveling_up: Res<LevelingUp>) -> bool {
+    !**leveling_up
+}
+
 fn leveling_up_transitions(
     leveling_up: Res<LevelingUp>,
     mut physics: ResMut<Time<Physics>>,
@@ -48,12 +59,30 @@ fn leveling_up_transitions(
 #[derive(Reflect, Message)]
 pub struct LeveledUp;
 
-fn gen_card_options(rng: &mut impl rand::RngExt) -> Vec<LevelUpCard> {
+/// Level ups that haven't had their cards shown yet, one screen of cards is shown at a time
+#[derive(Resource, Reflect, Default)]
+pub struct PendingLevelUps(u32);
+
+fn gen_card_options(
+    rng: &mut impl rand::RngExt,
+    skill_slots: &SkillSlots,
+    skill_library: &SkillLibrary,
+) -> Vec<LevelUpCard> {
+    let available = CardKind::ALL
+        .into_iter()
+        .filter(|kind| kind.is_available(skill_slots, skill_library))
+        .collect::<Vec<_>>();
     let mut options = Vec::new();
     for _ in 0..3 {
-        let kind: CardKind = rng.random();
+        let Ok(kind) = available.choose_weighted(rng, CardKind::weight) else {
+            warn!("No level up cards available");
+            break;
+        };
         let rarity: CardRarity = rng.random();
-        options.push(LevelUpCard { kind, rarity });
+        options.push(LevelUpCard {
+            kind: *kind,
+            rarity,
+        });
     }
     options
 }
@@ -61,18 +90,21 @@ fn gen_card_options(rng: &mut impl rand::RngExt) -> Vec<LevelUpCard> {
 fn level_up_events(
     mut commands: Commands,
     mut events: MessageReader<LeveledUp>,
+    mut pending: ResMut<PendingLevelUps>,
     mut leveling_up: ResMut<LevelingUp>,
     mut rng: Single<&mut WyRand, With<GlobalRng>>,
+    skill_slots: Single<&SkillSlots, With<Player>>,
+    skill_library: Res<SkillLibrary>,
 ) {
-    // Only consume events if not already leveling up
-    if !(**leveling_up) {
-        if let Some(LeveledUp) = events.read().next() {
-            leveling_up.0 = true;
-
-            commands.trigger(DisplayLevelUpCards {
-                options: gen_card_options(&mut rng),
-            });
-        }
+    pending.0 += events.read().count() as u32;
+    // The next screen opens once the current one has been closed
+    if !(**leveling_up) && pending.0 > 0 {
+        pending.0 -= 1;
+        leveling_up.0 = true;
+
+        commands.trigger(DisplayLevelUpCards {
+            options: gen_card_options(&mut rng, &skill_slots, &skill_library),
+        });
     }
 }
 
@@ -89,6 +121,15 @@ pub enum CardKind {
     IncreaseSpeed,
     IncreaseReloadRate,
     IncreasePenetration,
+    HomingArrows,
+    RicochetArrows,
+    ChainArrows,
+    IncreaseRange,
+    IncreaseProjectileSize,
+    IncreaseCritChance,
+    IncreaseCritMultiplier,
+    UnlockSkillSlot,
+    LearnSkill,
 }
 
 #[derive(Debug, Reflect, Clone, Copy)]
@@ -135,6 +176,31 @@ impl Distribution<CardRarity> for rand::distr::StandardUniform {
 }
 
 impl CardKind {
+    const ALL: [CardKind; 14] = [
+        CardKind::IncreaseHealth,
+        CardKind::IncreaseDamage,
+        CardKind::IncreaseSpeed,
+        CardKind::IncreaseReloadRate,
+        CardKind::IncreasePenetration,
+        CardKind::HomingArrows,
+        CardKind::RicochetArrows,
+        CardKind::ChainArrows,
+        CardKind::IncreaseRange,
+        CardKind::IncreaseProjectileSize,
+        CardKind::IncreaseCritChance,
+        CardKind::IncreaseCritMultiplier,
+        CardKind::UnlockSkillSlot,
+        CardKind::LearnSkill,
+    ];
+
+    /// Chance of being offered relative to the other available cards
+    fn weight(&self) -> f64 {
+        match self {
+            CardKind::UnlockSkillSlot | CardKind::LearnSkill => SKILL_CARD_WEIGHT,
+            _ => 1.0,
+        }
+    }
+
     pub fn description(&self) -> &str {
         match self {
             CardKind::IncreaseHealth => "Increase your maximum health.",
@@ -142,10 +208,35 @@ impl CardKind {
             CardKind::IncreaseSpeed => "Increase your movement speed.",
             CardKind::IncreaseReloadRate => "Decrease your reload time.",
             CardKind::IncreasePenetration => "Increase projectile penetration.",
+            CardKind::HomingArrows => "Arrows curve towards nearby enemies.",
+            CardKind::RicochetArrows => "Arrows bounce off walls.",
+            CardKind::ChainArrows => "Arrows jump to another enemy after a hit.",
+            CardKind::IncreaseRange => "Arrows fly further.",
+            CardKind::IncreaseProjectileSize => "Increase the size of your arrows.",
+            CardKind::IncreaseCritChance => "Increase your critical hit chance.",
+            CardKind::IncreaseCritMultiplier => "Critical hits deal more damage.",
+            CardKind::UnlockSkillSlot => "Unlock another skill slot.",
+            CardKind::LearnSkill => "Learn a new skill.",
         }
     }
 
-    pub fn apply(&self, rarity: CardRarity, player: &mut crate::player::PlayerParameters) {
+    /// Skill cards are only offered when there's somewhere for them to go
+    fn is_available(&self, skill_slots: &SkillSlots, skill_library: &SkillLibrary) -> bool {
+        match self {
+            CardKind::UnlockSkillSlot => skill_slots.has_locked_slot(),
+            CardKind::LearnSkill => {
+                skill_slots.has_empty_slot() && skill_library.has_unequipped(skill_slots)
+            }
+            _ => true,
+        }
+    }
+
+    pub fn apply(
+        &self,
+        rarity: CardRarity,
+        player: &mut crate::player::PlayerParameters,
+        commands: &mut Commands,
+    ) {
         match self {
             CardKind::IncreaseHealth => {
                 player.max_health += 20.0 * rarity.multiplier();
@@ -162,19 +253,67 @@ impl CardKind {
             CardKind::IncreasePenetration => {
                 player.projectile_pierce += 0.5 * (rarity.multiplier());
             }
+            CardKind::HomingArrows => {
+                player.projectile_homing += 1.5 * rarity.multiplier();
+            }
+            CardKind::RicochetArrows => {
+                player.projectile_bounces += 1.0 * rarity.multiplier();
+            }
+            CardKind::ChainArrows => {
+                player.projectile_chain += 1.0 * rarity.multiplier();
+            }
+            CardKind::IncreaseRange => {
+                player.projectile_range *= 1.2 * rarity.multiplier();
+            }
+            CardKind::IncreaseProjectileSize => {
+                player.projectile_size += 0.25 * rarity.multiplier();
+            }
+            CardKind::IncreaseCritChance => {
+                player.crit_chance = (player.crit_chance + 0.05 * rarity.multiplier()).min(1.0);
+            }
+            CardKind::IncreaseCritMultiplier => {
+                player.crit_multiplier += 0.25 * rarity.multiplier();
+            }
+            CardKind::UnlockSkillSlot => {
+                commands.trigger(UnlockSkillSlot);
+            }
+            CardKind::LearnSkill => {
+                commands.trigger(LearnSkill);
+            }
         }
     }
 }
 
-impl Distribution<CardKind> for rand::distr::StandardUniform {
-    fn sample<R: rand::Rng + rand::RngExt + ?Sized>(&self, rng: &mut R) -> CardKind {
-        let roll = rng.random_range(0..5);
-        match roll {
-            0 => CardKind::IncreaseHealth,
-            1 => CardKind::IncreaseDamage,
-            2 => CardKind::IncreaseSpeed,
-            3 => CardKind::IncreaseReloadRate,
-            _ => CardKind::IncreasePenetration,
-        }
+/// Skill cards are rarer than stat boosts, with both on offer one card in ten is a skill card
+const SKILL_CARD_WEIGHT: f64 = 2.0 / 3.0;
+
+#[test]
+fn only_available_cards_are_offered() {
+    use crate::player_skills::{SkillDefinition, SkillEffect, VolleyShape};
+    use rand::SeedableRng;
+
+    let definition = SkillDefinition {
+        name: "Multi-Shot".to_string(),
+        description: String::new(),
+        icon: 0,
+        cooldown_s: 1.0,
+        effect: SkillEffect::ArrowVolley {
+            arrow_count: 4,
+            shape: VolleyShape::Ring,
+        },
+    };
+    let mut skill_slots = SkillSlots::new(definition.to_skill("multi_shot"));
+    while skill_slots.unlock_next().is_some() {}
+    // Empty slots, but nothing left to learn
+    let skill_library = SkillLibrary::default();
+    let mut rng = WyRand::seed_from_u64(0);
+    for _ in 0..100 {
+        let options = gen_card_options(&mut rng, &skill_slots, &skill_library);
+        assert_eq!(options.len(), 3);
+        assert!(
+            options
+                .iter()
+                .all(|card| !matches!(card.kind, CardKind::UnlockSkillSlot | CardKind::LearnSkill))
+        );
     }
 }
@@ -1,15 +1,43 @@
-use std::{f32::consts::TAU, time::Duration};
+use std::{collections::HashMap, f32::consts::TAU, time::Duration};
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-use crate::{loading::TextureAssets, player::Player, projectile};
+use bevy_prng::WyRand;
+use bevy_rand::global::GlobalRng;
+use rand::seq::IndexedRandom;
+
+use crate::{
+    GameState,
+    core::{components::ExperienceLevel, status_effects::StatusEffect},
+    loading::{CustomAssets, TextureAssets},
+    player::{HOMING_ACQUISITION_RADIUS, Player},
+    player_levelup::not_leveling_up,
+    projectile::{
+        self, Homing, ProjectileBehaviour, Quiver, Team,
+        explosion::{Explosion, Explosive},
+        trap::{self, TrapStats},
+    },
+};
 
 pub struct PlayerSkillsPlugin;
 
 impl Plugin for PlayerSkillsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, SkillSlots::update_sys)
-            .add_observer(on_arrow_volley);
+        app.add_systems(
+            Update,
+            (
+                SkillSlots::update_sys.run_if(not_leveling_up),
+                unlock_slots_on_level_up,
+            ),
+        )
+        .add_systems(OnExit(GameState::Loading), insert_skill_library)
+        .add_observer(on_arrow_volley)
+        .add_observer(on_arrow_rain)
+        .add_observer(on_rapid_shot)
+        .add_observer(on_drop_trap)
+        .add_observer(on_unlock_skill_slot)
+        .add_observer(on_learn_skill);
     }
 }
 
@@ -33,25 +61,222 @@ impl<T> OptionOrLocked<T> {
 
 #[derive(Reflect, Clone, Debug)]
 pub struct Skill {
+    /// Id in the skill library
+    pub id: String,
     pub name: String,
     pub description: String,
-    //pub icon: Handle<Image>,
+    /// Index into the skill icons atlas
+    pub icon: usize,
     pub cooldown_timer: Timer,
     pub effect: SkillEffect,
+    /// Set while a skill that plays out over time is still going
+    pub active: Option<ActiveSkill>,
+}
+
+#[derive(Reflect, Clone, Debug)]
+pub struct ActiveSkill {
+    /// Fires every time this finishes
+    timer: Timer,
+    remaining: u32,
+    aim: Aim,
+    /// Fractions of an arrow owed to the quiver
+    quiver_debt: f32,
+}
+
+/// A skill as written in the custom assets file
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SkillDefinition {
+    pub name: String,
+    pub description: String,
+    pub icon: usize,
+    pub cooldown_s: f32,
+    pub effect: SkillEffect,
+}
+
+impl SkillDefinition {
+    pub fn to_skill(&self, id: &str) -> Skill {
+        let mut cooldown_timer = Timer::from_seconds(self.cooldown_s, TimerMode::Once);
+        // Ready to use as soon as it's equipped
+        cooldown_timer.finish();
+        Skill {
+            id: id.to_string(),
+            name: self.name.clone(),
+            description: self.description.clone(),
+            icon: self.icon,
+            cooldown_timer,
+            effect: self.effect.clone(),
+            active: None,
+        }
+    }
+}
+
+/// All skills, keyed by their id in the custom assets file. Loaded as an asset, then copied into
+/// a resource once loading is done so it can be looked up directly.
+#[derive(Asset, Resource, TypePath, Debug, Default, Clone)]
+pub struct SkillLibrary(pub HashMap<String, SkillDefinition>);
+
+impl SkillLibrary {
+    pub fn get(&self, id: &str) -> Option<&SkillDefinition> {
+        self.0.get(id)
+    }
+
+    /// A fresh instance of the skill with this id
+    pub fn skill(&self, id: &str) -> Result<Skill> {
+        Ok(self
+            .get(id)
+            .ok_or_else(|| anyhow::anyhow!("Unknown skill {id}"))?
+            .to_skill(id))
+    }
+
+    /// Ids of skills that aren't in any of the slots yet
+    fn unequipped<'a>(&'a self, skill_slots: &'a SkillSlots) -> impl Iterator<Item = &'a str> {
+        self.0
+            .keys()
+            .map(String::as_str)
+            .filter(|id| !skill_slots.has_skill(id))
+    }
+
+    pub fn has_unequipped(&self, skill_slots: &SkillSlots) -> bool {
+        self.unequipped(skill_slots).next().is_some()
+    }
+}
+
+fn insert_skill_library(
+    mut commands: Commands,
+    custom_assets: Res<CustomAssets>,
+    libraries: Res<Assets<SkillLibrary>>,
+) -> Result {
+    let library = libraries
+        .get(&custom_assets.skills)
+        .ok_or_else(|| anyhow::anyhow!("Skill library not loaded"))?;
+    commands.insert_resource(library.clone());
+    Ok(())
 }
 
 impl Skill {
     pub fn is_available(&self) -> bool {
-        self.cooldown_timer.is_finished()
+        self.cooldown_timer.is_finished() && self.active.is_none()
+    }
+
+    /// Ticks the cooldown, and any shots still to come from an active skill
+    fn tick(&mut self, delta: Duration, commands: &mut Commands) {
+        self.cooldown_timer.tick(delta);
+        let SkillEffect::RapidShot { quiver_cost, .. } = self.effect else {
+            return;
+        };
+        let Some(active) = self.active.as_mut() else {
+            return;
+        };
+        active.timer.tick(delta);
+        for _ in 0..active.timer.times_finished_this_tick() {
+            if active.remaining == 0 {
+                break;
+            }
+            active.remaining -= 1;
+            active.quiver_debt += quiver_cost;
+            let quiver_arrows = active.quiver_debt.floor();
+            active.quiver_debt -= quiver_arrows;
+            commands.trigger(SkillEventRapidShot {
+                aim: active.aim,
+                quiver_arrows: quiver_arrows as usize,
+            });
+        }
+        if active.remaining == 0 {
+            self.active = None;
+        }
     }
 
-    pub fn maybe_trigger(&mut self, commands: &mut Commands) -> bool {
+    pub fn maybe_trigger(&mut self, commands: &mut Commands, aim: Aim) -> bool {
         if self.is_available() {
             self.cooldown_timer.reset();
             match &self.effect {
-                SkillEffect::ArrowVolley { arrow_count } => {
+                SkillEffect::ArrowVolley { arrow_count, shape } => {
+                    commands.trigger(SkillEventArrowVolley {
+                        arrow_count: *arrow_count,
+                        shape: *shape,
+                        aim,
+                        homing: None,
+                        explosive: None,
+                    });
+                }
+                SkillEffect::HomingVolley {
+                    arrow_count,
+                    turn_rate,
+                    shape,
+                } => {
+                    commands.trigger(SkillEventArrowVolley {
+                        arrow_count: *arrow_count,
+                        shape: *shape,
+                        aim,
+                        homing: Some(Homing {
+                            turn_rate: *turn_rate,
+                            acquisition_radius: HOMING_ACQUISITION_RADIUS,
+                        }),
+                        explosive: None,
+                    });
+                }
+                SkillEffect::ExplosiveArrow {
+                    arrow_count,
+                    radius,
+                    edge_damage,
+                    shape,
+                } => {
                     commands.trigger(SkillEventArrowVolley {
                         arrow_count: *arrow_count,
+                        shape: *shape,
+                        aim,
+                        homing: None,
+                        explosive: Some(Explosive {
+                            radius: *radius,
+                            edge_damage: *edge_damage,
+                        }),
+                    });
+                }
+                SkillEffect::ArrowRain {
+                    radius,
+                    damage_multiplier,
+                } => {
+                    commands.trigger(SkillEventArrowRain {
+                        target: aim.target,
+                        radius: *radius,
+                        damage_multiplier: *damage_multiplier,
+                    });
+                }
+                SkillEffect::RapidShot {
+                    arrow_count,
+                    interval_s,
+                    ..
+                } => {
+                    // The first arrow goes out on the next tick, rather than waiting a whole
+                    // interval
+                    let mut timer = Timer::from_seconds(*interval_s, TimerMode::Repeating);
+                    timer.set_elapsed(timer.duration());
+                    self.active = Some(ActiveSkill {
+                        timer,
+                        remaining: *arrow_count,
+                        aim,
+                        quiver_debt: 0.0,
+                    });
+                }
+                SkillEffect::DropTrap {
+                    radius,
+                    arming_s,
+                    lifetime_s,
+                    charges,
+                    damage_multiplier,
+                    status_effect,
+                } => {
+                    commands.trigger(SkillEventDropTrap {
+                        position: aim.origin,
+                        stats: TrapStats {
+                            radius: *radius,
+                            arming_s: *arming_s,
+                            lifetime_s: *lifetime_s,
+                            charges: *charges,
+                            damage: 0.0,
+                            status_effect: *status_effect,
+                        },
+                        damage_multiplier: *damage_multiplier,
                     });
                 }
             }
@@ -68,9 +293,78 @@ pub struct SkillSlots {
     pub skill2: OptionOrLocked<Skill>,
     pub skill3: OptionOrLocked<Skill>,
     pub skill4: OptionOrLocked<Skill>,
+    /// How many of the level milestones have unlocked a slot so far
+    level_unlocks: usize,
 }
 
+/// Player levels at which another skill slot is unlocked
+const SLOT_UNLOCK_LEVELS: [u32; 3] = [5, 10, 15];
+
 impl SkillSlots {
+    /// Starts with just the first slot unlocked, holding `skill`
+    pub fn new(skill: Skill) -> Self {
+        SkillSlots {
+            skill1: OptionOrLocked::Some(skill),
+            skill2: OptionOrLocked::Locked,
+            skill3: OptionOrLocked::Locked,
+            skill4: OptionOrLocked::Locked,
+            level_unlocks: 0,
+        }
+    }
+
+    fn slots_mut(&mut self) -> [&mut OptionOrLocked<Skill>; 4] {
+        [
+            &mut self.skill1,
+            &mut self.skill2,
+            &mut self.skill3,
+            &mut self.skill4,
+        ]
+    }
+
+    fn slots(&self) -> [&OptionOrLocked<Skill>; 4] {
+        [&self.skill1, &self.skill2, &self.skill3, &self.skill4]
+    }
+
+    pub fn has_skill(&self, id: &str) -> bool {
+        self.slots()
+            .iter()
+            .any(|slot| matches!(slot, OptionOrLocked::Some(skill) if skill.id == id))
+    }
+
+    pub fn has_locked_slot(&self) -> bool {
+        self.slots()
+            .iter()
+            .any(|slot| matches!(slot, OptionOrLocked::Locked))
+    }
+
+    pub fn has_empty_slot(&self) -> bool {
+        self.slots()
+            .iter()
+            .any(|slot| matches!(slot, OptionOrLocked::None))
+    }
+
+    /// Unlocks the first locked slot, returning its number
+    pub fn unlock_next(&mut self) -> Option<u8> {
+        let (index, slot) = self
+            .slots_mut()
+            .into_iter()
+            .enumerate()
+            .find(|(_, slot)| matches!(slot, OptionOrLocked::Locked))?;
+        *slot = OptionOrLocked::None;
+        Some(index as u8 + 1)
+    }
+
+    /// Puts `skill` into the first empty slot, returning its number
+    pub fn equip(&mut self, skill: Skill) -> Option<u8> {
+        let (index, slot) = self
+            .slots_mut()
+            .into_iter()
+            .enumerate()
+            .find(|(_, slot)| matches!(slot, OptionOrLocked::None))?;
+        *slot = OptionOrLocked::Some(skill);
+        Some(index as u8 + 1)
+    }
+
     pub fn get_skill_slot_mut(&mut self, slot: u8) -> Option<&mut OptionOrLocked<Skill>> {
         match slot {
             1 => Some(&mut self.skill1),
@@ -91,35 +385,191 @@ impl SkillSlots {
         }
     }
 
-    fn update_one(slot: &mut OptionOrLocked<Skill>, time: Duration) {
+    fn update_one(slot: &mut OptionOrLocked<Skill>, time: Duration, commands: &mut Commands) {
         if let OptionOrLocked::Some(skill) = slot {
-            skill.cooldown_timer.tick(time);
+            skill.tick(time, commands);
         }
     }
-    fn update_sys(mut query: Query<&mut Self>, time: Res<Time>) {
+    fn update_sys(mut commands: Commands, mut query: Query<&mut Self>, time: Res<Time>) {
         for mut skill_slots in query.iter_mut() {
-            Self::update_one(&mut skill_slots.skill1, time.delta());
-            Self::update_one(&mut skill_slots.skill2, time.delta());
-            Self::update_one(&mut skill_slots.skill3, time.delta());
-            Self::update_one(&mut skill_slots.skill4, time.delta());
+            Self::update_one(&mut skill_slots.skill1, time.delta(), &mut commands);
+            Self::update_one(&mut skill_slots.skill2, time.delta(), &mut commands);
+            Self::update_one(&mut skill_slots.skill3, time.delta(), &mut commands);
+            Self::update_one(&mut skill_slots.skill4, time.delta(), &mut commands);
         }
     }
 }
 
-#[derive(Reflect, Clone, Debug)]
+fn unlock_slots_on_level_up(
+    mut query: Query<(&ExperienceLevel, &mut SkillSlots), Changed<ExperienceLevel>>,
+) {
+    for (experience_level, mut skill_slots) in query.iter_mut() {
+        let earned = SLOT_UNLOCK_LEVELS
+            .iter()
+            .filter(|level| experience_level.level >= **level)
+            .count();
+        while skill_slots.level_unlocks < earned {
+            skill_slots.level_unlocks += 1;
+            if let Some(slot) = skill_slots.unlock_next() {
+                info!(
+                    "Reached level {}, unlocked skill slot {slot}",
+                    experience_level.level
+                );
+            }
+        }
+    }
+}
+
+/// Unlocks the next locked skill slot
+#[derive(Event, Reflect, Clone, Debug)]
+pub struct UnlockSkillSlot;
+
+/// Equips a random skill the player doesn't have yet into an empty slot
+#[derive(Event, Reflect, Clone, Debug)]
+pub struct LearnSkill;
+
+fn on_unlock_skill_slot(
+    _trigger: On<UnlockSkillSlot>,
+    mut skill_slots: Single<&mut SkillSlots, With<Player>>,
+) {
+    match skill_slots.unlock_next() {
+        Some(slot) => info!("Unlocked skill slot {slot}"),
+        None => warn!("No locked skill slots left to unlock"),
+    }
+}
+
+fn on_learn_skill(
+    _trigger: On<LearnSkill>,
+    mut skill_slots: Single<&mut SkillSlots, With<Player>>,
+    skill_library: Res<SkillLibrary>,
+    mut rng: Single<&mut WyRand, With<GlobalRng>>,
+) -> Result {
+    // Sorted so the pick only depends on the rng, not on hash map ordering
+    let mut ids = skill_library.unequipped(&skill_slots).collect::<Vec<_>>();
+    ids.sort();
+    let Some(id) = ids.choose(&mut rng) else {
+        warn!("No skills left to learn");
+        return Ok(());
+    };
+    let skill = skill_library.skill(id)?;
+    let name = skill.name.clone();
+    match skill_slots.equip(skill) {
+        Some(slot) => info!("Learned {name} in skill slot {slot}"),
+        None => warn!("No empty skill slot for {name}"),
+    }
+    Ok(())
+}
+
+/// Where the player was aiming when a skill was used
+#[derive(Reflect, Clone, Copy, Debug)]
+pub struct Aim {
+    pub origin: Vec2,
+    pub direction: Vec2,
+    /// The point under the cursor
+    pub target: Vec2,
+}
+
+/// How the arrows of a volley are laid out around the aim direction
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub enum VolleyShape {
+    /// Evenly spread all the way around the player, starting from the aim direction
+    #[default]
+    Ring,
+    /// Fanned out across `angle` radians, centred on the aim direction
+    Cone { angle: f32 },
+    /// Side by side, all flying in the aim direction
+    Line { spacing: f32 },
+}
+
+impl VolleyShape {
+    /// Offset from the origin and direction of each arrow
+    fn arrows(&self, count: u32, direction: Vec2) -> Vec<(Vec2, Vec2)> {
+        // Centres the arrows on the aim direction, from -0.5 to 0.5 of the shape
+        let spread = |i: u32| {
+            if count > 1 {
+                i as f32 / (count - 1) as f32 - 0.5
+            } else {
+                0.0
+            }
+        };
+        (0..count)
+            .map(|i| match self {
+                VolleyShape::Ring => {
+                    let angle = TAU * i as f32 / count as f32;
+                    (Vec2::ZERO, Vec2::from_angle(angle).rotate(direction))
+                }
+                VolleyShape::Cone { angle } => (
+                    Vec2::ZERO,
+                    Vec2::from_angle(angle * spread(i)).rotate(direction),
+                ),
+                VolleyShape::Line { spacing } => {
+                    let width = spacing * (count.max(1) - 1) as f32;
+                    (direction.perp() * width * spread(i), direction)
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Reflect, Clone, Debug, Deserialize, Serialize)]
 pub enum SkillEffect {
-    ArrowVolley { arrow_count: u32 },
-    // Ideas for other skills:
-    // Rapid Shot (fires multiple projectiles in quick succession)
-    // Explosive Arrow (projectile explodes on impact, dealing area damage)
-    // Homing Arrow (projectile homes in on the nearest enemy)
-    // Drop Trap (places a trap on the ground that slows or damages enemies)
-    //
+    ArrowVolley {
+        arrow_count: u32,
+        #[serde(default)]
+        shape: VolleyShape,
+    },
+    /// A volley whose arrows home in on the nearest enemy
+    HomingVolley {
+        arrow_count: u32,
+        turn_rate: f32,
+        #[serde(default)]
+        shape: VolleyShape,
+    },
+    /// Arrows that explode on impact, damaging everything nearby
+    ExplosiveArrow {
+        arrow_count: u32,
+        radius: f32,
+        /// Fraction of the full damage dealt at the edge of the radius
+        edge_damage: f32,
+        #[serde(default)]
+        shape: VolleyShape,
+    },
+    /// Arrows land on the ground under the cursor, damaging everything in `radius`
+    ArrowRain {
+        radius: f32,
+        /// Of the player's arrow damage
+        damage_multiplier: f32,
+    },
+    /// Fires arrows one after another in the aim direction, `interval_s` apart
+    RapidShot {
+        arrow_count: u32,
+        interval_s: f32,
+        /// Arrows taken from the quiver for each one fired, 0 means the burst is free
+        #[serde(default)]
+        quiver_cost: f32,
+    },
+    /// Places a trap at the player's feet, which goes off on enemies that walk over it
+    DropTrap {
+        radius: f32,
+        arming_s: f32,
+        lifetime_s: f32,
+        charges: u32,
+        /// Of the player's arrow damage, 0 for a trap that only applies its status effect
+        #[serde(default)]
+        damage_multiplier: f32,
+        #[serde(default)]
+        status_effect: Option<StatusEffect>,
+    },
 }
 
 #[derive(Event, Reflect, Clone, Debug)]
 struct SkillEventArrowVolley {
     arrow_count: u32,
+    shape: VolleyShape,
+    aim: Aim,
+    /// Overrides the player's own homing
+    homing: Option<Homing>,
+    explosive: Option<Explosive>,
 }
 
 fn on_arrow_volley(
@@ -129,20 +579,141 @@ fn on_arrow_volley(
     textures: Res<TextureAssets>,
 ) {
     let (player_transform, player_params) = player.into_inner();
-    let base_direction = player_transform.rotation * Vec3::X;
-    let angle_step = TAU / (trigger.arrow_count as f32);
-    let mut angle = 0.0;
-    for _i in 0..trigger.arrow_count {
-        let direction = Quat::from_rotation_z(angle) * base_direction;
+    let mut stats = player_params.projectile_stats();
+    if let Some(homing) = trigger.homing {
+        stats.behaviour = ProjectileBehaviour::Homing(homing);
+    }
+    if let Some(explosive) = trigger.explosive {
+        stats.explosive = Some(explosive);
+    }
+    for (offset, direction) in trigger
+        .shape
+        .arrows(trigger.arrow_count, trigger.aim.direction)
+    {
+        projectile::spawn_projectile(
+            &mut commands,
+            (trigger.aim.origin + offset).extend(player_transform.translation.z),
+            direction,
+            stats,
+            &textures,
+        );
+    }
+}
+
+#[derive(Event, Reflect, Clone, Debug)]
+struct SkillEventRapidShot {
+    aim: Aim,
+    /// Taken from the quiver for this arrow
+    quiver_arrows: usize,
+}
+
+fn on_rapid_shot(
+    trigger: On<SkillEventRapidShot>,
+    mut commands: Commands,
+    player: Single<(&Transform, &crate::player::PlayerParameters, &mut Quiver), With<Player>>,
+    textures: Res<TextureAssets>,
+) {
+    let (player_transform, player_params, mut quiver) = player.into_inner();
+    // Fires from wherever the player is now, keeping the direction from when it started
+    if quiver.try_take_n(trigger.quiver_arrows) {
         projectile::spawn_projectile(
             &mut commands,
             player_transform.translation,
-            direction.truncate(),
-            player_params.projectile_speed,
-            player_params.projectile_damage,
-            player_params.projectile_pierce.floor() as u32,
+            trigger.aim.direction,
+            player_params.projectile_stats(),
             &textures,
         );
-        angle += angle_step;
     }
 }
+
+#[derive(Event, Reflect, Clone, Debug)]
+struct SkillEventDropTrap {
+    position: Vec2,
+    /// Damage is filled in from the player's arrow damage
+    stats: TrapStats,
+    damage_multiplier: f32,
+}
+
+fn on_drop_trap(
+    trigger: On<SkillEventDropTrap>,
+    mut commands: Commands,
+    player_params: Single<&crate::player::PlayerParameters, With<Player>>,
+    textures: Res<TextureAssets>,
+) {
+    let stats = TrapStats {
+        damage: player_params.projectile_damage * trigger.damage_multiplier,
+        ..trigger.stats
+    };
+    trap::spawn_trap(&mut commands, trigger.position, stats, &textures);
+}
+
+#[derive(Event, Reflect, Clone, Debug)]
+struct SkillEventArrowRain {
+    target: Vec2,
+    radius: f32,
+    damage_multiplier: f32,
+}
+
+fn on_arrow_rain(
+    trigger: On<SkillEventArrowRain>,
+    player: Single<(&Transform, &crate::player::PlayerParameters), With<Player>>,
+    mut explosions: MessageWriter<Explosion>,
+) {
+    let (player_transform, player_params) = player.into_inner();
+    // Can't land any further away than an arrow could fly
+    let player_position = player_transform.translation.truncate();
+    let target = player_position
+        + (trigger.target - player_position).clamp_length_max(player_params.projectile_range);
+    explosions.write(Explosion {
+        position: target,
+        explosive: Explosive {
+            radius: trigger.radius,
+            edge_damage: 1.0,
+        },
+        damage: player_params.projectile_damage * trigger.damage_multiplier,
+        is_crit: false,
+        team: Team::Player,
+        status_effect: None,
+        exclude: None,
+    });
+}
+
+#[test]
+fn unlocking_and_equipping_slots_in_order() {
+    let definition = SkillDefinition {
+        name: "Multi-Shot".to_string(),
+        description: String::new(),
+        icon: 0,
+        cooldown_s: 1.0,
+        effect: SkillEffect::ArrowVolley {
+            arrow_count: 4,
+            shape: VolleyShape::Ring,
+        },
+    };
+    let mut skill_slots = SkillSlots::new(definition.to_skill("multi_shot"));
+    assert!(skill_slots.equip(definition.to_skill("other")).is_none());
+    assert_eq!(skill_slots.unlock_next(), Some(2));
+    assert_eq!(skill_slots.unlock_next(), Some(3));
+    assert_eq!(skill_slots.equip(definition.to_skill("other")), Some(2));
+    assert!(skill_slots.has_skill("other"));
+    assert!(skill_slots.has_empty_slot());
+    assert_eq!(skill_slots.unlock_next(), Some(4));
+    assert_eq!(skill_slots.unlock_next(), None);
+}
+
+#[test]
+fn volley_shapes_centre_on_aim_direction() {
+    let aim = Vec2::Y;
+    let cone = VolleyShape::Cone { angle: 1.0 }.arrows(3, aim);
+    assert!(cone[1].1.abs_diff_eq(aim, 1e-5));
+    assert!((cone[0].1.angle_to(cone[2].1) - 1.0).abs() < 1e-5);
+
+    let line = VolleyShape::Line { spacing: 4.0 }.arrows(3, aim);
+    assert!(line.iter().all(|(_, direction)| *direction == aim));
+    assert!(line[0].0.abs_diff_eq(Vec2::new(4.0, 0.0), 1e-5));
+    assert!(line[1].0.abs_diff_eq(Vec2::ZERO, 1e-5));
+
+    let ring = VolleyShape::Ring.arrows(4, aim);
+    assert!(ring[0].1.abs_diff_eq(aim, 1e-5));
+    assert!(ring[2].1.abs_diff_eq(-aim, 1e-5));
+}
@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+
+use crate::enemy::steering::EnemySpatialHash;
+
+/// Makes a projectile jump to another nearby enemy after each hit
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+pub struct Chain {
+    pub jumps: u32,
+    /// How far away the next enemy can be
+    pub range: f32,
+    /// Damage is multiplied by this on every jump
+    pub damage_decay: f32,
+}
+
+impl Chain {
+    /// The closest enemy in range that hasn't been hit yet
+    pub fn next_target(
+        &self,
+        spatial_hash: &EnemySpatialHash,
+        projectile: Entity,
+        position: Vec2,
+        already_hit: &[Entity],
+    ) -> Option<Vec2> {
+        spatial_hash
+            .neighbours(projectile, position, self.range)
+            .filter(|(enemy, _)| !already_hit.contains(enemy))
+            .map(|(_, enemy_position)| enemy_position)
+            .min_by(|a, b| {
+                a.distance_squared(position)
+                    .total_cmp(&b.distance_squared(position))
+            })
+    }
+}
+
+#[test]
+fn chains_to_the_closest_enemy_not_yet_hit() {
+    let [projectile, near, far, out_of_range] =
+        [0, 1, 2, 3].map(Entity::from_raw_u32).map(Option::unwrap);
+    let mut spatial_hash = EnemySpatialHash::default();
+    spatial_hash.insert(near, Vec2::new(10.0, 0.0));
+    spatial_hash.insert(far, Vec2::new(0.0, 30.0));
+    spatial_hash.insert(out_of_range, Vec2::new(100.0, 0.0));
+    let chain = Chain {
+        jumps: 2,
+        range: 50.0,
+        damage_decay: 0.7,
+    };
+    let next = |already_hit: &[Entity]| {
+        chain.next_target(&spatial_hash, projectile, Vec2::ZERO, already_hit)
+    };
+    assert_eq!(next(&[]), Some(Vec2::new(10.0, 0.0)));
+    assert_eq!(next(&[near]), Some(Vec2::new(0.0, 30.0)));
+    assert_eq!(next(&[near, far]), None);
+}
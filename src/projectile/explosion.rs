@@ -0,0 +1,119 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use super::{EnemyHit, PlayerHit, Team};
+use crate::{core::status_effects::StatusEffect, enemy::Enemy, player::Player};
+
+pub struct ExplosionPlugin;
+
+impl Plugin for ExplosionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<Explosion>()
+            .register_type::<Explosion>()
+            .add_systems(Update, explode.after(super::projectile_collision_system));
+    }
+}
+
+/// Makes a projectile explode when it hits something
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+pub struct Explosive {
+    pub radius: f32,
+    /// Fraction of the full damage dealt at the edge of the radius
+    pub edge_damage: f32,
+}
+
+#[derive(Message, Reflect, Debug)]
+pub struct Explosion {
+    pub position: Vec2,
+    pub explosive: Explosive,
+    pub damage: f32,
+    pub is_crit: bool,
+    pub team: Team,
+    pub status_effect: Option<StatusEffect>,
+    /// Something that already took a direct hit, and so isn't damaged again
+    pub exclude: Option<Entity>,
+}
+
+impl Explosion {
+    fn damage_at(&self, distance: f32) -> f32 {
+        let t = (distance / self.explosive.radius).clamp(0.0, 1.0);
+        self.damage * 1.0.lerp(self.explosive.edge_damage, t)
+    }
+}
+
+fn explode(
+    mut explosions: MessageReader<Explosion>,
+    spatial_query: SpatialQuery,
+    enemies: Query<&Transform, With<Enemy>>,
+    player: Single<(Entity, &Transform), With<Player>>,
+    mut enemy_hits: MessageWriter<EnemyHit>,
+    mut player_hits: MessageWriter<PlayerHit>,
+) {
+    let (player_entity, player_transform) = *player;
+    for explosion in explosions.read() {
+        println!("Explosion at {:?}", explosion.position);
+        let shape = Collider::circle(explosion.explosive.radius);
+        let hits = spatial_query.shape_intersections(
+            &shape,
+            explosion.position,
+            0.0,
+            &SpatialQueryFilter::default(),
+        );
+        for entity in hits {
+            if Some(entity) == explosion.exclude {
+                continue;
+            }
+            match explosion.team {
+                Team::Player => {
+                    if let Ok(transform) = enemies.get(entity) {
+                        let distance = transform
+                            .translation
+                            .truncate()
+                            .distance(explosion.position);
+                        enemy_hits.write(EnemyHit {
+                            enemy_entity: entity,
+                            damage: explosion.damage_at(distance),
+                            is_crit: explosion.is_crit,
+                            status_effect: explosion.status_effect,
+                            knockback: Vec2::ZERO,
+                        });
+                    }
+                }
+                Team::Enemy => {
+                    if entity == player_entity {
+                        let distance = player_transform
+                            .translation
+                            .truncate()
+                            .distance(explosion.position);
+                        player_hits.write(PlayerHit {
+                            damage: explosion.damage_at(distance),
+                            source: None,
+                            status_effect: explosion.status_effect,
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn explosion_damage_falls_off_to_the_edge() {
+    let explosion = Explosion {
+        position: Vec2::ZERO,
+        explosive: Explosive {
+            radius: 20.0,
+            edge_damage: 0.3,
+        },
+        damage: 10.0,
+        is_crit: false,
+        team: Team::Player,
+        status_effect: None,
+        exclude: None,
+    };
+    assert_eq!(explosion.damage_at(0.0), 10.0);
+    assert!((explosion.damage_at(10.0) - 6.5).abs() < 1e-5);
+    assert!((explosion.damage_at(20.0) - 3.0).abs() < 1e-5);
+    // Anything caught past the radius takes the edge damage, not less
+    assert!((explosion.damage_at(40.0) - 3.0).abs() < 1e-5);
+}
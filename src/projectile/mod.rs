@@ -0,0 +1,626 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_prng::WyRand;
+use bevy_rand::global::GlobalRng;
+use rand::RngExt;
+
+use crate::{
+    camera::GAME_RENDER_LAYER,
+    core::{
+        body,
+        components::{Death, ItemStore},
+        status_effects::StatusEffect,
+    },
+    enemy::{Enemy, steering::EnemySpatialHash},
+    player::Player,
+};
+
+pub mod chain;
+pub mod explosion;
+pub mod trap;
+
+use chain::Chain;
+use explosion::{Explosion, Explosive};
+
+/// Which side fired a projectile, and so who it can hit
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Team {
+    Player,
+    Enemy,
+}
+
+impl Team {
+    fn tint(&self) -> Color {
+        match self {
+            Team::Player => Color::WHITE,
+            Team::Enemy => Color::srgb(1.0, 0.4, 0.4),
+        }
+    }
+}
+
+#[derive(Component, Reflect)]
+pub struct Projectile {
+    velocity: Vec3,
+    damage: f32,
+    crit_chance: f32,
+    crit_multiplier: f32,
+    // Pierce of 1 means it hits one target and is destroyed.
+    pierce: u32,
+    /// Wall hits it can bounce off before being destroyed
+    bounces: u32,
+    team: Team,
+    status_effect: Option<StatusEffect>,
+    explosive: Option<Explosive>,
+    chain: Option<Chain>,
+    /// Enemies this has already hit, which it won't hit again
+    hit: Vec<Entity>,
+    /// Distance it can fly before fading out
+    range: f32,
+    travelled: f32,
+    /// Where it was last frame, so only real movement counts towards its range
+    last_position: Vec2,
+    knockback: f32,
+}
+
+/// Everything needed to describe a projectile, independent of where it's fired from
+#[derive(Reflect, Clone, Copy, Debug)]
+pub struct ProjectileStats {
+    pub speed: f32,
+    pub damage: f32,
+    /// Chance from 0 to 1 for a hit to be a critical hit
+    pub crit_chance: f32,
+    /// Damage multiplier applied on a critical hit
+    pub crit_multiplier: f32,
+    pub pierce: u32,
+    pub bounces: u32,
+    pub team: Team,
+    /// Applied to whatever the projectile hits
+    pub status_effect: Option<StatusEffect>,
+    pub behaviour: ProjectileBehaviour,
+    pub explosive: Option<Explosive>,
+    pub chain: Option<Chain>,
+    pub range: f32,
+    /// Scale of the sprite and collider
+    pub size: f32,
+    /// Impulse given to whatever it hits, along its direction of travel
+    pub knockback: f32,
+}
+
+/// Range of enemy projectiles, which don't have stats of their own for it
+pub const ENEMY_PROJECTILE_RANGE: f32 = 250.0;
+/// Projectiles fade out over this last fraction of their range
+const FADE_FRACTION: f32 = 0.2;
+/// Damage rolls land anywhere within this fraction either side of the base damage
+const DAMAGE_SPREAD: f32 = 0.1;
+
+/// How a projectile moves after it's fired
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq)]
+pub enum ProjectileBehaviour {
+    #[default]
+    Straight,
+    Homing(Homing),
+}
+
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+pub struct Homing {
+    /// Radians per second
+    pub turn_rate: f32,
+    /// Only targets within this distance are picked up
+    pub acquisition_radius: f32,
+}
+
+/// What a homing projectile is currently turning towards
+#[derive(Component, Reflect, Default)]
+pub struct HomingTarget(Option<Entity>);
+
+#[derive(Component, Reflect)]
+pub struct ProjectileCollider;
+
+pub struct ProjectilePlugin;
+
+impl Plugin for ProjectilePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((explosion::ExplosionPlugin, trap::TrapPlugin))
+            .add_systems(
+                Update,
+                (
+                    //projectile_movement_system,
+                    projectile_collision_system,
+                    homing_projectiles,
+                    projectile_range,
+                    Quiver::system,
+                ),
+            )
+            .add_message::<EnemyHit>()
+            .add_message::<PlayerHit>()
+            .add_message::<Reloaded>()
+            .register_type::<Projectile>()
+            .register_type::<ProjectileCollider>()
+            .register_type::<ProjectileBehaviour>()
+            .register_type::<HomingTarget>()
+            .register_type::<EnemyHit>()
+            .register_type::<PlayerHit>();
+    }
+}
+
+pub fn spawn_projectile(
+    commands: &mut Commands,
+    position: Vec3,
+    direction: Vec2,
+    stats: ProjectileStats,
+    textures: &crate::loading::TextureAssets,
+) {
+    let rotation = Vec2::X.angle_to(direction);
+    let velocity = direction.extend(0.0) * stats.speed;
+    let mut transform =
+        // Colliders pick up the transform's scale, so this sizes the hitbox along with the sprite
+        Transform::from_translation(position).with_scale(Vec2::splat(0.5 * stats.size).extend(1.0));
+    transform.rotate_z(rotation);
+    let mut projectile = commands.spawn((
+        transform,
+        Sprite {
+            color: stats.team.tint(),
+            ..Sprite::from_image(textures.arrow.clone())
+        },
+        Projectile {
+            velocity,
+            damage: stats.damage,
+            crit_chance: stats.crit_chance,
+            crit_multiplier: stats.crit_multiplier,
+            pierce: stats.pierce,
+            bounces: stats.bounces,
+            team: stats.team,
+            status_effect: stats.status_effect,
+            explosive: stats.explosive,
+            chain: stats.chain,
+            hit: Vec::new(),
+            range: stats.range,
+            travelled: 0.0,
+            last_position: position.truncate(),
+            knockback: stats.knockback,
+        },
+        LinearVelocity(velocity.truncate()),
+        Name::new("Projectile"),
+        body::body(body::BodyKind::Dynamic),
+        Collider::rectangle(12.0, 2.0),
+        Sensor,
+        //DebugRender::default(),
+        GAME_RENDER_LAYER,
+    ));
+    if let ProjectileBehaviour::Homing(_) = stats.behaviour {
+        projectile.insert((stats.behaviour, HomingTarget::default()));
+    }
+}
+
+#[derive(Debug, Message, Reflect)]
+pub struct EnemyHit {
+    pub enemy_entity: Entity,
+    pub damage: f32,
+    pub is_crit: bool,
+    pub status_effect: Option<StatusEffect>,
+    /// Impulse to push the enemy with
+    pub knockback: Vec2,
+}
+
+#[derive(Debug, Message, Reflect)]
+pub struct PlayerHit {
+    pub damage: f32,
+    /// The enemy that dealt the damage, if it came from one directly
+    pub source: Option<Entity>,
+    pub status_effect: Option<StatusEffect>,
+}
+
+/// A quiver has finished reloading
+#[derive(Debug, Message, Reflect)]
+pub struct Reloaded {
+    pub entity: Entity,
+}
+
+struct ProjectileHit {
+    projectile_entity: Entity,
+    hit_entity: Entity,
+}
+fn entity_for_projectile_collision(
+    entity1: Entity,
+    entity2: Entity,
+    projectiles: &Query<ProjectileData>,
+) -> Option<ProjectileHit> {
+    match (projectiles.get(entity1), projectiles.get(entity2)) {
+        (Ok(_), Ok(_)) => None,
+        (_, Ok(_)) => Some(ProjectileHit {
+            projectile_entity: entity2,
+            hit_entity: entity1,
+        }),
+        (Ok(_), _) => Some(ProjectileHit {
+            projectile_entity: entity1,
+            hit_entity: entity2,
+        }),
+        _ => None,
+    }
+}
+
+type ProjectileData<'a> = (
+    &'a mut Projectile,
+    &'a mut Transform,
+    &'a mut LinearVelocity,
+    Option<&'a mut HomingTarget>,
+);
+
+impl Projectile {
+    /// Rolls the damage for a single hit, and whether it crit
+    fn roll_damage(&self, rng: &mut impl RngExt) -> (f32, bool) {
+        let spread = rng.random_range(1.0 - DAMAGE_SPREAD..=1.0 + DAMAGE_SPREAD);
+        let is_crit = rng.random_bool(self.crit_chance.clamp(0.0, 1.0) as f64);
+        let crit_multiplier = if is_crit { self.crit_multiplier } else { 1.0 };
+        (self.damage * spread * crit_multiplier, is_crit)
+    }
+
+    fn explosion(
+        &self,
+        transform: &Transform,
+        exclude: Option<Entity>,
+        (damage, is_crit): (f32, bool),
+    ) -> Option<Explosion> {
+        self.explosive.map(|explosive| Explosion {
+            position: transform.translation.truncate(),
+            explosive,
+            damage,
+            is_crit,
+            team: self.team,
+            status_effect: self.status_effect,
+            exclude,
+        })
+    }
+}
+
+#[expect(clippy::too_many_arguments)]
+fn projectile_collision_system(
+    mut commands: Commands,
+    mut collisions: MessageReader<CollisionStart>,
+    contacts: Collisions,
+    mut projectiles: Query<ProjectileData>,
+    spatial_hash: Res<EnemySpatialHash>,
+    enemies: Query<Entity, With<Enemy>>,
+    walls: Query<Entity, With<crate::walls::WallCollider>>,
+    player: Single<Entity, With<Player>>,
+    mut enemy_hits: MessageWriter<EnemyHit>,
+    mut player_hits: MessageWriter<PlayerHit>,
+    mut explosions: MessageWriter<Explosion>,
+    mut rng: Single<&mut WyRand, With<GlobalRng>>,
+) -> Result {
+    for event in collisions.read() {
+        if let Some(ProjectileHit {
+            projectile_entity,
+            hit_entity,
+        }) = entity_for_projectile_collision(event.collider1, event.collider2, &projectiles)
+        {
+            let team = projectiles.get(projectile_entity)?.0.team;
+            if team == Team::Player && enemies.get(hit_entity).is_ok() {
+                println!("Projectile hit Enemy {hit_entity:?}");
+                let (mut projectile, mut transform, mut linear_velocity, homing_target) =
+                    projectiles.get_mut(projectile_entity)?;
+                if projectile.pierce == 0 || projectile.hit.contains(&hit_entity) {
+                    // Projectiles can trigger multiple collision events in a single frame, so
+                    // we need to check if it's already been "spent". Chaining projectiles also
+                    // fly back through enemies they've already hit.
+                    continue;
+                }
+                let (damage, is_crit) = projectile.roll_damage(&mut rng);
+                enemy_hits.write(EnemyHit {
+                    enemy_entity: hit_entity,
+                    damage,
+                    is_crit,
+                    status_effect: projectile.status_effect,
+                    knockback: linear_velocity.0.normalize_or_zero() * projectile.knockback,
+                });
+                // Explodes on every enemy it pierces, not just the last
+                if let Some(explosion) =
+                    projectile.explosion(&transform, Some(hit_entity), (damage, is_crit))
+                {
+                    explosions.write(explosion);
+                }
+                projectile.hit.push(hit_entity);
+                let next_target =
+                    projectile
+                        .chain
+                        .filter(|chain| chain.jumps > 0)
+                        .and_then(|chain| {
+                            chain.next_target(
+                                &spatial_hash,
+                                projectile_entity,
+                                transform.translation.truncate(),
+                                &projectile.hit,
+                            )
+                        });
+                if let Some(mut homing_target) = homing_target {
+                    // Look for something new rather than circling back to the same enemy
+                    homing_target.0 = None;
+                }
+                if let (Some(mut chain), Some(target)) = (projectile.chain, next_target) {
+                    // Jumping doesn't use up pierce
+                    chain.jumps -= 1;
+                    projectile.chain = Some(chain);
+                    projectile.damage *= chain.damage_decay;
+                    let direction = (target - transform.translation.truncate()).normalize_or_zero();
+                    linear_velocity.0 = direction * linear_velocity.0.length();
+                    projectile.velocity = linear_velocity.0.extend(0.0);
+                    transform.rotation = Quat::from_rotation_z(Vec2::X.angle_to(direction));
+                    continue;
+                }
+                projectile.pierce = projectile.pierce.saturating_sub(1);
+                if projectile.pierce == 0 {
+                    commands.entity(projectile_entity).despawn();
+                }
+            } else if team == Team::Enemy && hit_entity == *player {
+                println!("Projectile hit Player {hit_entity:?}");
+                let (mut projectile, transform, _, _) = projectiles.get_mut(projectile_entity)?;
+                if projectile.pierce == 0 {
+                    continue;
+                }
+                let roll = projectile.roll_damage(&mut rng);
+                player_hits.write(PlayerHit {
+                    damage: roll.0,
+                    source: None,
+                    status_effect: projectile.status_effect,
+                });
+                if let Some(explosion) = projectile.explosion(&transform, Some(hit_entity), roll) {
+                    explosions.write(explosion);
+                }
+                // Pierce only applies to the player's own projectiles
+                projectile.pierce = 0;
+                commands.entity(projectile_entity).despawn();
+            } else if enemies.get(hit_entity).is_ok() || hit_entity == *player {
+                // Projectiles pass through their own team
+            } else if walls.get(hit_entity).is_ok() {
+                println!("Projectile hit Wall {hit_entity:?}");
+                let (mut projectile, mut transform, mut linear_velocity, _) =
+                    projectiles.get_mut(projectile_entity)?;
+                if projectile.pierce == 0 {
+                    continue;
+                }
+                let roll = projectile.roll_damage(&mut rng);
+                if let Some(explosion) = projectile.explosion(&transform, None, roll) {
+                    explosions.write(explosion);
+                }
+                if projectile.bounces > 0 {
+                    projectile.bounces -= 1;
+                    let normal = contacts
+                        .get(event.collider1, event.collider2)
+                        .and_then(|contact_pair| contact_pair.manifolds.first())
+                        .map(|manifold| manifold.normal);
+                    let velocity = match normal {
+                        Some(normal) => linear_velocity.0.reflect(normal),
+                        None => -linear_velocity.0,
+                    };
+                    linear_velocity.0 = velocity;
+                    projectile.velocity = velocity.extend(0.0);
+                    transform.rotation = Quat::from_rotation_z(Vec2::X.angle_to(velocity));
+                    continue;
+                }
+                // Destroy projectile on wall hit
+                projectile.pierce = 0;
+                commands.entity(projectile_entity).despawn();
+            } else {
+                println!("Projectile hit something else {hit_entity:?}");
+            }
+        }
+    }
+    Ok(())
+}
+
+type HomingProjectileData<'a> = (
+    Entity,
+    &'a mut Projectile,
+    &'a ProjectileBehaviour,
+    &'a mut HomingTarget,
+    &'a mut Transform,
+    &'a mut LinearVelocity,
+);
+
+type LivingTarget = (Without<Projectile>, Without<Death>);
+
+fn homing_projectiles(
+    time: Res<Time>,
+    spatial_hash: Res<EnemySpatialHash>,
+    mut projectiles: Query<HomingProjectileData>,
+    enemies: Query<&Transform, (With<Enemy>, LivingTarget)>,
+    player: Single<(Entity, &Transform), (With<Player>, LivingTarget)>,
+) {
+    let (player_entity, player_transform) = *player;
+    for (
+        entity,
+        mut projectile,
+        behaviour,
+        mut homing_target,
+        mut transform,
+        mut linear_velocity,
+    ) in projectiles.iter_mut()
+    {
+        let ProjectileBehaviour::Homing(homing) = behaviour else {
+            continue;
+        };
+        let position = transform.translation.truncate();
+        let direction = linear_velocity.0.normalize_or_zero();
+        let target_position = |target: Entity| match projectile.team {
+            Team::Player => enemies
+                .get(target)
+                .ok()
+                .map(|transform| transform.translation.truncate()),
+            Team::Enemy => Some(player_transform.translation.truncate()),
+        };
+        // Retarget once the current target has died
+        let mut target = homing_target.0.and_then(target_position);
+        if target.is_none() {
+            let candidate = match projectile.team {
+                Team::Player => spatial_hash
+                    .neighbours(entity, position, homing.acquisition_radius)
+                    // Piercing arrows would otherwise circle the enemy they just went through
+                    .filter(|(enemy, _)| !projectile.hit.contains(enemy))
+                    // Prefer whatever is ahead, rather than turning around
+                    .filter(|(_, enemy_position)| direction.dot(*enemy_position - position) > 0.0)
+                    .min_by(|(_, a), (_, b)| {
+                        a.distance_squared(position)
+                            .total_cmp(&b.distance_squared(position))
+                    }),
+                Team::Enemy => Some((player_entity, player_transform.translation.truncate()))
+                    .filter(|(_, player_position)| {
+                        player_position.distance(position) <= homing.acquisition_radius
+                    }),
+            };
+            homing_target.0 = candidate.map(|(entity, _)| entity);
+            target = candidate.map(|(_, position)| position);
+        }
+        let Some(target) = target else {
+            continue;
+        };
+        let desired = (target - position).normalize_or_zero();
+        let max_turn = homing.turn_rate * time.delta_secs();
+        let turn = direction.angle_to(desired).clamp(-max_turn, max_turn);
+        let new_direction = Vec2::from_angle(turn).rotate(direction);
+        let speed = linear_velocity.0.length();
+        linear_velocity.0 = new_direction * speed;
+        projectile.velocity = linear_velocity.0.extend(0.0);
+        transform.rotation = Quat::from_rotation_z(Vec2::X.angle_to(new_direction));
+    }
+}
+
+fn projectile_range(
+    mut commands: Commands,
+    mut projectiles: Query<(Entity, &mut Projectile, &Transform, &mut Sprite)>,
+) {
+    // Measured from the position rather than velocity and time, so arrows frozen by paused physics
+    // don't run out of range
+    for (entity, mut projectile, transform, mut sprite) in projectiles.iter_mut() {
+        let position = transform.translation.truncate();
+        projectile.travelled += position.distance(projectile.last_position);
+        projectile.last_position = position;
+        let remaining = 1.0 - projectile.travelled / projectile.range;
+        if remaining <= 0.0 {
+            commands.entity(entity).despawn();
+        } else if remaining < FADE_FRACTION {
+            sprite.color.set_alpha(remaining / FADE_FRACTION);
+        }
+    }
+}
+
+#[derive(Reflect, Component)]
+pub struct Quiver {
+    store: ItemStore,
+    reload_timer: Timer,
+}
+
+impl Quiver {
+    pub fn new(max_arrows: usize, reload_duration: std::time::Duration) -> Self {
+        let mut reload_timer = Timer::new(reload_duration, TimerMode::Once);
+        reload_timer.finish();
+        Quiver {
+            store: ItemStore::new(max_arrows),
+            reload_timer,
+        }
+    }
+
+    pub fn set_max(&mut self, max: usize) {
+        self.store.max = max;
+        if self.store.current > max {
+            self.store.current = max;
+        }
+    }
+    pub fn set_reload_delay(&mut self, duration: std::time::Duration) {
+        self.reload_timer.set_duration(duration);
+    }
+
+    pub fn current(&self) -> usize {
+        self.store.current
+    }
+    pub fn max(&self) -> usize {
+        self.store.max
+    }
+
+    pub fn is_reloading(&self) -> bool {
+        !self.reload_timer.is_finished()
+    }
+
+    pub fn reload_pct(&self) -> Option<f32> {
+        if self.is_reloading() {
+            Some(self.reload_timer.fraction())
+        } else {
+            None
+        }
+    }
+
+    pub fn pct(&self) -> f32 {
+        self.store.pct()
+    }
+
+    pub fn try_take(&mut self) -> bool {
+        self.try_take_n(1)
+    }
+
+    /// Takes `amount` arrows, or none at all if there aren't that many
+    pub fn try_take_n(&mut self, amount: usize) -> bool {
+        let taken = self.store.try_take_n(amount);
+
+        if taken && amount > 0 && self.store.current == 0 {
+            info!("Quiver empty, starting reload timer");
+            self.reload_timer.reset();
+        }
+
+        taken
+    }
+
+    /// Returns true when a reload has just finished
+    fn update(&mut self, time: &Res<Time>) -> bool {
+        self.reload_timer.tick(time.delta());
+        if self.reload_timer.just_finished() {
+            self.store.reset();
+            return true;
+        }
+        false
+    }
+
+    fn system(
+        mut query: Query<(Entity, &mut Self)>,
+        time: Res<Time>,
+        mut reloads: MessageWriter<Reloaded>,
+    ) {
+        for (entity, mut quiver) in query.iter_mut() {
+            if quiver.update(&time) {
+                reloads.write(Reloaded { entity });
+            }
+        }
+    }
+}
+
+#[test]
+fn damage_rolls_stay_in_spread_and_crit_by_chance() {
+    use rand::SeedableRng;
+
+    let projectile = |crit_chance| Projectile {
+        velocity: Vec3::X,
+        damage: 10.0,
+        crit_chance,
+        crit_multiplier: 2.0,
+        pierce: 1,
+        bounces: 0,
+        team: Team::Player,
+        status_effect: None,
+        explosive: None,
+        chain: None,
+        hit: Vec::new(),
+        range: 100.0,
+        travelled: 0.0,
+        last_position: Vec2::ZERO,
+        knockback: 0.0,
+    };
+    let mut rng = WyRand::seed_from_u64(0);
+    let (low, high) = (10.0 * (1.0 - DAMAGE_SPREAD), 10.0 * (1.0 + DAMAGE_SPREAD));
+    for _ in 0..100 {
+        let (damage, is_crit) = projectile(0.0).roll_damage(&mut rng);
+        assert!(!is_crit);
+        assert!((low..=high).contains(&damage));
+
+        let (damage, is_crit) = projectile(1.0).roll_damage(&mut rng);
+        assert!(is_crit);
+        assert!((low * 2.0..=high * 2.0).contains(&damage));
+    }
+}
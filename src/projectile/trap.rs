@@ -0,0 +1,201 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use super::EnemyHit;
+use crate::{
+    GameState,
+    camera::GAME_RENDER_LAYER,
+    core::status_effects::StatusEffect,
+    enemy::{ENEMY_Z, Enemy},
+    loading::TextureAssets,
+    player_levelup::not_leveling_up,
+};
+
+pub struct TrapPlugin;
+
+impl Plugin for TrapPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Trap>().add_systems(
+            Update,
+            (tick_traps.run_if(not_leveling_up), trap_collision_system)
+                .chain()
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// Everything needed to describe a trap, independent of where it's placed
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+pub struct TrapStats {
+    pub radius: f32,
+    /// Time after being placed before it can go off
+    pub arming_s: f32,
+    /// Time before it disappears, whether or not it has gone off
+    pub lifetime_s: f32,
+    /// Times it can go off before it's used up
+    pub charges: u32,
+    pub damage: f32,
+    /// Applied to every enemy it goes off on
+    pub status_effect: Option<StatusEffect>,
+}
+
+/// Sits on the ground and goes off on enemies that walk over it
+#[derive(Component, Reflect)]
+pub struct Trap {
+    arming_timer: Timer,
+    lifetime_timer: Timer,
+    charges: u32,
+    damage: f32,
+    status_effect: Option<StatusEffect>,
+    /// Enemies this has already gone off on, which it won't go off on again
+    hit: Vec<Entity>,
+}
+
+/// Under enemies and their spawn markers
+const TRAP_Z: f32 = ENEMY_Z - 2.0;
+/// Radius of the trap sprite, which the transform is scaled from
+const TRAP_SPRITE_RADIUS: f32 = 16.0;
+const ARMING_COLOR: Color = Color::srgba(1.0, 0.8, 0.3, 0.3);
+const ARMED_COLOR: Color = Color::srgba(1.0, 0.8, 0.3, 0.9);
+
+impl Trap {
+    fn new(stats: TrapStats) -> Self {
+        Trap {
+            arming_timer: Timer::from_seconds(stats.arming_s, TimerMode::Once),
+            lifetime_timer: Timer::from_seconds(stats.lifetime_s, TimerMode::Once),
+            charges: stats.charges,
+            damage: stats.damage,
+            status_effect: stats.status_effect,
+            hit: Vec::new(),
+        }
+    }
+
+    fn is_armed(&self) -> bool {
+        self.arming_timer.is_finished()
+    }
+
+    fn is_spent(&self) -> bool {
+        self.charges == 0 || self.lifetime_timer.is_finished()
+    }
+
+    /// Uses up a charge going off on `enemy`, if it's able to
+    fn trigger(&mut self, enemy: Entity) -> Option<EnemyHit> {
+        if !self.is_armed() || self.is_spent() || self.hit.contains(&enemy) {
+            return None;
+        }
+        self.charges -= 1;
+        self.hit.push(enemy);
+        Some(EnemyHit {
+            enemy_entity: enemy,
+            damage: self.damage,
+            is_crit: false,
+            status_effect: self.status_effect,
+            knockback: Vec2::ZERO,
+        })
+    }
+}
+
+pub fn spawn_trap(
+    commands: &mut Commands,
+    position: Vec2,
+    stats: TrapStats,
+    textures: &TextureAssets,
+) {
+    commands.spawn((
+        Name::new("Trap"),
+        // Colliders pick up the transform's scale, so this sizes the trigger area with the sprite
+        Transform::from_translation(position.extend(TRAP_Z))
+            .with_scale(Vec2::splat(stats.radius / TRAP_SPRITE_RADIUS).extend(1.0)),
+        Sprite {
+            color: ARMING_COLOR,
+            ..Sprite::from_image(textures.cursor_crosshair.clone())
+        },
+        Trap::new(stats),
+        Collider::circle(TRAP_SPRITE_RADIUS),
+        Sensor,
+        CollisionEventsEnabled,
+        GAME_RENDER_LAYER,
+    ));
+}
+
+fn tick_traps(
+    mut commands: Commands,
+    time: Res<Time>,
+    contacts: Collisions,
+    mut traps: Query<(Entity, &mut Trap, &mut Sprite)>,
+    enemies: Query<(), With<Enemy>>,
+    mut enemy_hits: MessageWriter<EnemyHit>,
+) {
+    for (entity, mut trap, mut sprite) in traps.iter_mut() {
+        trap.arming_timer.tick(time.delta());
+        trap.lifetime_timer.tick(time.delta());
+        if trap.arming_timer.just_finished() {
+            sprite.color = ARMED_COLOR;
+            // Enemies already standing on it never start a new collision, so catch them here
+            for enemy in contacts
+                .entities_colliding_with(entity)
+                .filter(|colliding| enemies.contains(*colliding))
+            {
+                if let Some(hit) = trap.trigger(enemy) {
+                    enemy_hits.write(hit);
+                }
+            }
+        }
+        if trap.is_spent() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn trap_collision_system(
+    mut commands: Commands,
+    mut collisions: MessageReader<CollisionStart>,
+    mut traps: Query<&mut Trap>,
+    enemies: Query<(), With<Enemy>>,
+    mut enemy_hits: MessageWriter<EnemyHit>,
+) {
+    for event in collisions.read() {
+        let (trap_entity, hit_entity) = match (
+            traps.contains(event.collider1),
+            traps.contains(event.collider2),
+        ) {
+            (true, false) => (event.collider1, event.collider2),
+            (false, true) => (event.collider2, event.collider1),
+            _ => continue,
+        };
+        if !enemies.contains(hit_entity) {
+            continue;
+        }
+        let Ok(mut trap) = traps.get_mut(trap_entity) else {
+            continue;
+        };
+        if let Some(hit) = trap.trigger(hit_entity) {
+            println!("Trap went off on Enemy {hit_entity:?}");
+            enemy_hits.write(hit);
+            if trap.is_spent() {
+                commands.entity(trap_entity).despawn();
+            }
+        }
+    }
+}
+
+#[test]
+fn traps_go_off_once_armed_until_out_of_charges() {
+    let mut trap = Trap::new(TrapStats {
+        radius: 8.0,
+        arming_s: 0.5,
+        lifetime_s: 10.0,
+        charges: 2,
+        damage: 5.0,
+        status_effect: None,
+    });
+    let [a, b, c] = [1, 2, 3].map(Entity::from_raw_u32).map(Option::unwrap);
+    assert!(trap.trigger(a).is_none());
+
+    trap.arming_timer.tick(std::time::Duration::from_secs(1));
+    assert!(trap.trigger(a).is_some());
+    assert!(trap.trigger(a).is_none());
+    assert!(trap.trigger(b).is_some());
+    assert!(trap.is_spent());
+    assert!(trap.trigger(c).is_none());
+}
@@ -10,6 +10,7 @@ use leafwing_input_manager::prelude::ActionState;
 
 use crate::{
     core::components::{ExperienceLevel, Health},
+    damage_numbers::DamageNumberSettings,
     input::Action,
     player::PlayerParameters,
     projectile::Quiver,
@@ -21,7 +22,7 @@ impl Plugin for GameUiPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             EguiPrimaryContextPass,
-            (params_ui, physics_ui).run_if(in_state(crate::GameState::Playing)),
+            (params_ui, physics_ui, settings_ui).run_if(in_state(crate::GameState::Playing)),
         );
     }
 }
@@ -101,17 +102,55 @@ fn params_ui(
                         .range(1..=255)
                         .ui(ui);
                     ui.end_row();
+                    ui.label("Projectile Bounces:");
+                    DragValue::new(&mut player.projectile_bounces)
+                        .range(0..=255)
+                        .ui(ui);
+                    ui.end_row();
+                    ui.label("Projectile Chain:");
+                    DragValue::new(&mut player.projectile_chain)
+                        .range(0..=255)
+                        .ui(ui);
+                    ui.end_row();
                     ui.label("Projectile Size:");
                     DragValue::new(&mut player.projectile_size)
                         .range(0.1..=10.0)
                         .ui(ui);
                     ui.label(format!("{:.2}", player.projectile_size));
                     ui.end_row();
+                    ui.label("Projectile Range:");
+                    DragValue::new(&mut player.projectile_range)
+                        .range(1.0..=2000.0)
+                        .ui(ui);
+                    ui.end_row();
                     ui.label("Projectile Damage:");
                     DragValue::new(&mut player.projectile_damage)
                         .range(0.1..=1000.0)
                         .ui(ui);
                     ui.end_row();
+                    ui.label("Projectile Homing:");
+                    DragValue::new(&mut player.projectile_homing)
+                        .range(0.0..=20.0)
+                        .ui(ui);
+                    ui.end_row();
+                    ui.label("Crit Chance:");
+                    DragValue::new(&mut player.crit_chance)
+                        .range(0.0..=1.0)
+                        .speed(0.01)
+                        .ui(ui);
+                    ui.end_row();
+                    ui.label("Crit Multiplier:");
+                    DragValue::new(&mut player.crit_multiplier)
+                        .range(1.0..=10.0)
+                        .speed(0.05)
+                        .ui(ui);
+                    ui.end_row();
+                    ui.label("Knockback:");
+                    DragValue::new(&mut player.projectile_knockback)
+                        .range(0.0..=500.0)
+                        .speed(1.0)
+                        .ui(ui);
+                    ui.end_row();
                     ui.label("Quiver Size:");
                     DragValue::new(&mut player.quiver_size)
                         .range(1..=100)
@@ -127,6 +166,19 @@ fn params_ui(
     Ok(())
 }
 
+fn settings_ui(
+    mut context: EguiContexts,
+    mut damage_number_settings: ResMut<DamageNumberSettings>,
+) -> Result {
+    egui::Window::new("Settings")
+        .collapsible(true)
+        .default_open(false)
+        .show(context.ctx_mut()?, |ui| {
+            ui.checkbox(&mut damage_number_settings.enabled, "Damage numbers");
+        });
+    Ok(())
+}
+
 fn physics_ui(
     mut context: EguiContexts,
     mut physics: ResMut<Time<Physics>>,
@@ -18,7 +18,9 @@ impl Plugin for InGameHudPlugin {
                     health::update_health_ui,
                     reloading::update_reloading_ui,
                     abilities::update_reloading_icon_overlay,
+                    abilities::update_ability_icons,
                     timer::update_timers,
+                    boss_health::update_boss_health_ui,
                 )
                     .run_if(in_state(GameState::Playing)),
             );
@@ -39,7 +41,8 @@ fn setup(mut commands: Commands, ui_textures: Res<UiTextureAssets>) {
             (health::health_ui_bundle()),
             (reloading::reloading_ui_bundle(&ui_textures)),
             (abilities::ability_ui_bundle(&ui_textures)),
-            (timer::timer_ui_bundle())
+            (timer::timer_ui_bundle()),
+            (boss_health::boss_health_ui_bundle())
         ],
     ));
 }
@@ -295,23 +298,57 @@ mod reloading {
 mod abilities {
     use bevy::prelude::*;
 
-    use crate::{core::ui_components::debug_ui_background, loading::UiTextureAssets};
+    use crate::{
+        core::ui_components::debug_ui_background,
+        loading::UiTextureAssets,
+        player_skills::{OptionOrLocked, SkillSlots},
+    };
 
     #[derive(Component, Reflect)]
     pub struct IconReloadingOverlay {
+        pub slot: u8,
         pub max_height: f32,
     }
 
+    /// Shows the icon of the skill in a slot
+    #[derive(Component, Reflect)]
+    pub struct AbilityIcon {
+        pub slot: u8,
+    }
+
+    const LOCKED_COLOR: Color = Color::srgba(0.1, 0.1, 0.1, 0.8);
+    const EMPTY_COLOR: Color = Color::srgba(0.5, 0.5, 0.5, 0.3);
+
+    pub(super) fn update_ability_icons(
+        mut query: Query<(&mut ImageNode, &AbilityIcon)>,
+        skill_slots: Single<&SkillSlots>,
+    ) {
+        for (mut image, icon) in query.iter_mut() {
+            let color = match skill_slots.get_skill_slot(icon.slot) {
+                Some(OptionOrLocked::Some(skill)) => {
+                    if let Some(atlas) = image.texture_atlas.as_mut()
+                        && atlas.index != skill.icon
+                    {
+                        atlas.index = skill.icon;
+                    }
+                    Color::WHITE
+                }
+                Some(OptionOrLocked::None) => EMPTY_COLOR,
+                Some(OptionOrLocked::Locked) | None => LOCKED_COLOR,
+            };
+            if image.color != color {
+                image.color = color;
+            }
+        }
+    }
+
     pub(super) fn update_reloading_icon_overlay(
         mut query: Query<(&mut Node, &IconReloadingOverlay)>,
-        skill_slots: Single<&crate::player_skills::SkillSlots>,
+        skill_slots: Single<&SkillSlots>,
     ) {
         for (mut node, overlay) in query.iter_mut() {
-            let pct = skill_slots.get_skill_slot(1);
-            let pct = match pct {
-                Some(crate::player_skills::OptionOrLocked::Some(skill)) => {
-                    skill.cooldown_timer.fraction_remaining()
-                }
+            let pct = match skill_slots.get_skill_slot(overlay.slot) {
+                Some(OptionOrLocked::Some(skill)) => skill.cooldown_timer.fraction_remaining(),
                 _ => 0.0,
             };
             node.height = Val::Px(overlay.max_height * pct);
@@ -319,7 +356,7 @@ mod abilities {
     }
 
     /// This bundle is a grey overlay over the skill icon that covers to show cooldown.
-    fn reloading_icon_overlay_bundle() -> impl Bundle {
+    fn reloading_icon_overlay_bundle(slot: u8) -> impl Bundle {
         (
             Node {
                 width: Val::Px(40.0),
@@ -329,7 +366,33 @@ mod abilities {
                 ..default()
             },
             BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
-            IconReloadingOverlay { max_height: 40.0 },
+            IconReloadingOverlay {
+                slot,
+                max_height: 40.0,
+            },
+        )
+    }
+
+    /// Bundle for a single skill slot's icon
+    fn ability_icon_bundle(textures: &UiTextureAssets, slot: u8) -> impl Bundle {
+        (
+            ImageNode {
+                image: textures.skill_icons.clone(),
+                texture_atlas: Some(TextureAtlas {
+                    layout: textures.skill_icons_layout.clone(),
+                    index: 0,
+                }),
+                color: LOCKED_COLOR,
+                ..default()
+            },
+            Node {
+                width: Val::Px(40.0),
+                height: Val::Px(40.0),
+                ..default()
+            },
+            Name::new(format!("Ability Slot {slot}")),
+            AbilityIcon { slot },
+            children![reloading_icon_overlay_bundle(slot)],
         )
     }
 
@@ -344,26 +407,17 @@ mod abilities {
                 justify_content: JustifyContent::Center,
                 align_content: AlignContent::Center,
                 flex_direction: FlexDirection::Row,
+                column_gap: Val::Px(5.0),
                 ..default()
             },
             debug_ui_background(),
             Name::new("Ability UI"),
-            children![(
-                ImageNode {
-                    image: textures.skill_icons.clone(),
-                    texture_atlas: Some(TextureAtlas {
-                        layout: textures.skill_icons_layout.clone(),
-                        index: 1
-                    }),
-                    ..default()
-                },
-                Node {
-                    width: Val::Px(40.0),
-                    height: Val::Px(40.0),
-                    ..default()
-                },
-                children![reloading_icon_overlay_bundle()],
-            )],
+            children![
+                ability_icon_bundle(textures, 1),
+                ability_icon_bundle(textures, 2),
+                ability_icon_bundle(textures, 3),
+                ability_icon_bundle(textures, 4),
+            ],
         )
     }
 }
@@ -413,6 +467,91 @@ mod timer {
     }
 }
 
+mod boss_health {
+    use bevy::prelude::*;
+
+    use crate::{
+        core::{components::Health, ui_components::debug_ui_background},
+        enemy::boss::Boss,
+    };
+
+    #[derive(Component, Reflect)]
+    pub(super) struct BossHealthUi;
+    #[derive(Component, Reflect)]
+    pub(super) struct BossHealthUiFill;
+    #[derive(Component, Reflect)]
+    pub(super) struct BossHealthUiName;
+
+    /// Bundle for the boss health bar along the top of the screen, hidden when there's no boss
+    pub(super) fn boss_health_ui_bundle() -> impl Bundle {
+        (
+            Node {
+                width: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                top: Val::Px(50.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                display: Display::None,
+                ..default()
+            },
+            debug_ui_background(),
+            Name::new("Boss Health UI"),
+            BossHealthUi,
+            children![
+                (
+                    Text::new(""),
+                    Name::new("Boss Name Text"),
+                    TextFont {
+                        font_size: 24.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                    BossHealthUiName,
+                ),
+                (
+                    Node {
+                        width: Val::Percent(50.0),
+                        height: Val::Px(16.0),
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BorderColor::all(Color::BLACK),
+                    BackgroundColor(Color::srgb(0.2, 0.2, 0.2)),
+                    Name::new("Boss Health Bar"),
+                    children![(
+                        Node {
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.8, 0.1, 0.1)),
+                        Name::new("Boss Health Bar Fill"),
+                        BossHealthUiFill,
+                    )],
+                )
+            ],
+        )
+    }
+
+    pub(super) fn update_boss_health_ui(
+        mut container: Single<&mut Node, (With<BossHealthUi>, Without<BossHealthUiFill>)>,
+        mut fill: Single<&mut Node, (With<BossHealthUiFill>, Without<BossHealthUi>)>,
+        mut name: Single<&mut Text, With<BossHealthUiName>>,
+        bosses: Query<(&Boss, &Health)>,
+    ) {
+        match bosses.iter().next() {
+            Some((boss, health)) => {
+                container.display = Display::Flex;
+                fill.width = Val::Percent(health.pct().clamp(0.0, 1.0) * 100.0);
+                if name.0 != boss.definition.name {
+                    name.0 = boss.definition.name.clone();
+                }
+            }
+            None => container.display = Display::None,
+        }
+    }
+}
+
 fn teardown(mut commands: Commands, hud_query: Query<Entity, With<InGameHud>>) {
     for hud_entity in hud_query.iter() {
         commands.entity(hud_entity).despawn_children().despawn();
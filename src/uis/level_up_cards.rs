@@ -42,7 +42,8 @@ fn card_interaction(
                 image_node.color = Color::srgb(0.1, 0.2, 0.4).into();
                 button.set_changed();
                 leveling_up.0 = false;
-                card.kind.apply(card.rarity, &mut player_params);
+                card.kind
+                    .apply(card.rarity, &mut player_params, &mut commands);
                 commands.trigger(DespawnLevelUpCards);
             }
             Interaction::Hovered => {